@@ -1,9 +1,13 @@
 use crate::die;
+use crate::recv;
 use async_channel::Receiver;
 use async_channel::Sender;
 use async_channel::TryRecvError;
+use async_channel::TrySendError;
+use bevy_ecs::entity::EntityAllocator;
 use bevy_ecs::prelude::*;
 use bevy_ecs::world::CommandQueue;
+use bevy_platform::sync::Arc;
 use std::fmt;
 
 /// The object-safe equivalent of a `Box<dyn Command>`.
@@ -86,6 +90,29 @@ impl fmt::Debug for CommandQueueBuilder {
 	}
 }
 
+/// The initial number of `Entity` ids that are kept reserved for every command channel, so that
+/// entities can be spawned from the async context without waiting for the world to allocate their
+/// ids. The pool grows when more ids than this are taken in a single frame.
+const RESERVED_ENTITIES: usize = 16;
+
+/// Creates a new command channel to the given world, with a full pool of reserved `Entity` ids.
+pub(crate) fn channel(world: &World) -> (CommandQueueSender, CommandQueueReceiver) {
+	let (queue_tx, queue_rx) = async_channel::unbounded();
+	let (reserved_tx, reserved_rx) = async_channel::unbounded();
+	let receiver = CommandQueueReceiver {
+		queues: queue_rx,
+		reserved_tx,
+		reserved_rx: reserved_rx.clone(),
+		capacity: RESERVED_ENTITIES,
+	};
+	receiver.top_up(world.entities_allocator());
+	// the receiver is kept in an `Arc` because it is not `Unpin`
+	(
+		CommandQueueSender(queue_tx, Arc::new(reserved_rx)),
+		receiver,
+	)
+}
+
 /// Use this to send commands (stored in `CommandQueue`s) directly to the Bevy World, where they will
 /// be applied during the Last schedule.
 ///
@@ -93,14 +120,16 @@ impl fmt::Debug for CommandQueueBuilder {
 /// Single commands can still be sent with `CommandQueueSender::send_single()`.
 ///
 /// The easiest way to get a `CommandQueueSender` is with `AsyncWorld::sender()`.
-#[derive(Clone, Debug)]
-pub struct CommandQueueSender(Sender<CommandQueue>);
+#[derive(Clone)]
+pub struct CommandQueueSender(Sender<CommandQueue>, Arc<Receiver<Entity>>);
 
-impl CommandQueueSender {
-	pub(crate) fn new(inner: Sender<CommandQueue>) -> Self {
-		Self(inner)
+impl fmt::Debug for CommandQueueSender {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("CommandQueueSender").field(&self.0).finish()
 	}
+}
 
+impl CommandQueueSender {
 	/// Sends an `CommandQueue` directly to the Bevy `World`, where they will be applied during
 	/// the `Last` schedule.
 	pub async fn send_queue(&self, inner_queue: CommandQueue) {
@@ -112,27 +141,77 @@ impl CommandQueueSender {
 	pub async fn send_single(&self, single: BoxedCommand) {
 		self.send_queue(single.into()).await;
 	}
+
+	/// Sends a `CommandQueue` to the Bevy `World` without waiting. This is meant for `Drop`
+	/// implementations. Does nothing if the `World` no longer exists.
+	pub(crate) fn try_send_queue(&self, inner_queue: CommandQueue) {
+		if let Err(e @ TrySendError::Full(_)) = self.0.try_send(inner_queue) {
+			die(e)
+		}
+	}
+
+	/// Takes a reserved `Entity` id, waiting for the pool to be refilled if it is empty.
+	pub(crate) async fn reserve_entity(&self) -> Entity {
+		recv(Receiver::clone(&self.1)).await
+	}
+
+	/// Takes a reserved `Entity` id, or returns `None` if the pool is empty.
+	pub(crate) fn try_reserve_entity(&self) -> Option<Entity> {
+		self.1.try_recv().ok()
+	}
 }
 
 #[derive(Component)]
-pub(crate) struct CommandQueueReceiver(Receiver<CommandQueue>);
+pub(crate) struct CommandQueueReceiver {
+	queues: Receiver<CommandQueue>,
+	reserved_tx: Sender<Entity>,
+	reserved_rx: Receiver<Entity>,
+	capacity: usize,
+}
 
 impl CommandQueueReceiver {
-	pub(crate) fn new(receiver: Receiver<CommandQueue>) -> Self {
-		Self(receiver)
+	/// Grows the pool of reserved `Entity` ids to twice the number of ids that were taken since it
+	/// was last topped up (if that is larger), then tops it up.
+	fn reserve_entities(&mut self, allocator: &EntityAllocator) {
+		let taken = self.capacity.saturating_sub(self.reserved_tx.len());
+		self.capacity = self.capacity.max(2 * taken);
+		self.top_up(allocator);
+	}
+
+	fn top_up(&self, allocator: &EntityAllocator) {
+		while self.reserved_tx.len() < self.capacity {
+			self.reserved_tx
+				.try_send(allocator.alloc())
+				.unwrap_or_else(die);
+		}
+	}
+
+	/// Returns a `Command` that frees the reserved `Entity` ids that were never taken.
+	fn free_reserved_entities(&self) -> impl Command {
+		let reserved = self.reserved_rx.clone();
+		move |world: &mut World| {
+			while let Ok(id) = reserved.try_recv() {
+				world.entities_allocator_mut().free(id);
+			}
+		}
 	}
 }
 
 pub(crate) fn receive_and_apply_commands(
 	mut commands: Commands,
-	receivers: Query<(Entity, &CommandQueueReceiver)>,
+	mut receivers: Query<(Entity, &mut CommandQueueReceiver)>,
+	allocator: &EntityAllocator,
 ) {
-	for (id, receiver) in receivers.iter() {
+	for (id, mut receiver) in receivers.iter_mut() {
 		loop {
-			match receiver.0.try_recv() {
+			match receiver.queues.try_recv() {
 				Ok(mut command_queue) => commands.append(&mut command_queue),
-				Err(TryRecvError::Empty) => break,
+				Err(TryRecvError::Empty) => {
+					receiver.reserve_entities(allocator);
+					break;
+				}
 				Err(TryRecvError::Closed) => {
+					commands.queue(receiver.free_reserved_entities());
 					commands.entity(id).despawn();
 					break;
 				}
@@ -235,4 +314,99 @@ mod tests {
 		assert_eq!(4, sender.0.sender_count());
 		assert_eq!(4, other_sender.0.sender_count());
 	}
+
+	#[test]
+	fn reserved() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		let fut = async move {
+			let parent = async_world.try_reserve_entity().unwrap();
+			let child = async_world.try_reserve_entity().unwrap();
+			async_world
+				.start_queue()
+				.push(move |world: &mut World| {
+					world.spawn_empty_at(parent).unwrap();
+					world.spawn_at(child, ChildOf(parent)).unwrap();
+				})
+				.apply()
+				.await;
+
+			// drain the pool, then wait for it to be refilled
+			while async_world.try_reserve_entity().is_some() {}
+			let refilled = async_world.reserve_entity().await;
+			sender.send((parent, child, refilled)).await.unwrap();
+		};
+		AsyncComputeTaskPool::get().spawn(fut).detach();
+
+		let (parent, child, refilled) = loop {
+			match receiver.try_recv() {
+				Ok(ids) => break ids,
+				Err(_) => app.update(),
+			}
+		};
+
+		let child_of = app.world().get::<ChildOf>(child).unwrap();
+		assert_eq!(parent, child_of.parent());
+		assert!(app.world().get_entity(refilled).is_err());
+		assert!(![parent, child].contains(&refilled));
+	}
+
+	#[test]
+	fn spawn_many() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		// more entities than the initial pool holds
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let mut ids = Vec::new();
+				for i in 0..100 {
+					ids.push(async_world.spawn(Counter(i)).await.id());
+				}
+				sender.send(ids).await.unwrap();
+			})
+			.detach();
+		let ids = loop {
+			match receiver.try_recv() {
+				Ok(ids) => break ids,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+
+		let mut counters = app.world_mut().query::<&Counter>();
+		assert_eq!(100, counters.iter(app.world()).count());
+		for (i, id) in ids.into_iter().enumerate() {
+			assert_eq!(i, app.world().get::<Counter>(id).unwrap().0 as usize);
+		}
+	}
+
+	#[test]
+	fn growing_pool() {
+		let world = World::new();
+		let (sender, mut receiver) = channel(&world);
+		let mut take_and_refill = |taken| {
+			for _ in 0..taken {
+				sender.try_reserve_entity().unwrap();
+			}
+			receiver.reserve_entities(world.entities_allocator());
+			sender.1.len()
+		};
+
+		assert_eq!(RESERVED_ENTITIES, sender.1.len());
+		// taking up to half of the pool does not grow it
+		assert_eq!(16, take_and_refill(8));
+		// taking more grows it to twice the number of ids taken
+		assert_eq!(24, take_and_refill(12));
+		assert_eq!(48, take_and_refill(24));
+		// and it never shrinks
+		assert_eq!(48, take_and_refill(0));
+	}
 }
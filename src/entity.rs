@@ -1,23 +1,45 @@
 use crate::command::CommandQueueSender;
-use crate::die;
+use crate::error::AsyncEcsError;
+use crate::error::EntityDespawned;
 use crate::recv;
+use crate::timeout::Timeout;
+use crate::timeout::with_timeout;
+use crate::util::add_related;
 use crate::util::despawn;
+use crate::util::despawn_if_exists;
 use crate::util::insert;
 use crate::util::remove;
+use crate::util::try_despawn;
+use crate::util::try_insert;
+use crate::util::try_remove;
+use crate::wait_for::StartObserving;
 use crate::wait_for::StartWaitingFor;
+use crate::wait_for::StartWaitingForChange;
+use crate::wait_for::StartWaitingForDespawn;
+use crate::wait_for::StartWaitingForRemoval;
+use crate::wait_for::WatchingForDespawn;
 use crate::world::AsyncWorld;
 use async_channel::Receiver;
-use async_channel::Sender;
-use async_channel::TrySendError;
+use bevy_ecs::component::ComponentId;
+use bevy_ecs::component::Mutable;
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
+use bevy_ecs::query::ReadOnlyQueryData;
+use bevy_ecs::relationship::Relationship;
+use futures_lite::Stream;
+use futures_lite::StreamExt;
 use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::pin::pin;
 
 /// Represents an `Entity` that can be manipulated asynchronously.
 ///
 /// The easiest way to get an `AsyncEntity` is with `AsyncWorld::spawn_empty()`.
 ///
 /// Dropping the `AsyncEntity` **WILL NOT** despawn the corresponding entity in the synchronous world.
-/// Use `AsyncEntity::despawn` to despawn an entity asynchronously.
+/// Use `AsyncEntity::despawn` to despawn an entity asynchronously, or `AsyncWorld::spawn_owned()`
+/// to despawn it on drop instead.
 #[derive(Debug)]
 pub struct AsyncEntity {
 	id: Entity,
@@ -40,21 +62,184 @@ impl AsyncEntity {
 	}
 
 	/// Recursively despawns the represented entity.
+	///
+	/// This also despawns all of its descendants (and any other entities related to it through a
+	/// `linked_spawn` relationship).
 	pub async fn despawn(self) {
 		self.world.apply(despawn(self.id)).await;
 	}
 
+	/// Recursively despawns the represented entity. Returns an error if the entity does not exist.
+	pub async fn try_despawn(self) -> Result<(), AsyncEcsError> {
+		self.world.with_world(try_despawn(self.id)).await
+	}
+
 	/// Adds a `Bundle` of components to the entity. This will overwrite any previous value(s) of
 	/// the same component type.
 	pub async fn insert<B: Bundle>(&self, bundle: B) {
 		self.world.apply(insert(self.id, bundle)).await;
 	}
 
+	/// Adds a `Bundle` of components to the entity. Returns an error if the entity does not exist.
+	pub async fn try_insert<B: Bundle>(&self, bundle: B) -> Result<(), AsyncEcsError> {
+		self.world.with_world(try_insert(self.id, bundle)).await
+	}
+
 	/// Removes a `Bundle` of components from the entity.
 	pub async fn remove<B: Bundle>(&self) {
 		self.world.apply(remove::<B>(self.id)).await;
 	}
 
+	/// Removes a `Bundle` of components from the entity. Returns an error if the entity does not exist.
+	pub async fn try_remove<B: Bundle>(&self) -> Result<(), AsyncEcsError> {
+		self.world.with_world(try_remove::<B>(self.id)).await
+	}
+
+	/// Returns whether the entity currently exists in the world.
+	///
+	/// The world may still change before any following operation is applied. Use the `try_*`
+	/// family to handle entities that may be despawned at any time.
+	pub async fn exists(&self) -> bool {
+		let id = self.id;
+		self.world
+			.with_world_ref(move |world: &World| world.get_entity(id).is_ok())
+			.await
+	}
+
+	/// Returns whether the entity currently has the `Component` of a given type. Returns `false` if
+	/// the entity does not exist.
+	pub async fn has<C: Component>(&self) -> bool {
+		let id = self.id;
+		self.world
+			.with_world_ref(move |world: &World| {
+				world
+					.get_entity(id)
+					.is_ok_and(|entity| entity.contains::<C>())
+			})
+			.await
+	}
+
+	/// Returns the id and name of every `Component` that the entity currently has. Returns an
+	/// empty `Vec` if the entity does not exist.
+	///
+	/// The marker component that `AsyncEntity::wait_for_despawn()` inserts is left out.
+	pub async fn archetype_components(&self) -> Vec<(ComponentId, String)> {
+		let id = self.id;
+		self.world
+			.with_world_ref(move |world: &World| {
+				let marker = world.component_id::<WatchingForDespawn>();
+				world
+					.inspect_entity(id)
+					.map(|infos| {
+						infos
+							.filter(|info| Some(info.id()) != marker)
+							.map(|info| (info.id(), info.name().to_string()))
+							.collect()
+					})
+					.unwrap_or_default()
+			})
+			.await
+	}
+
+	/// Returns the current value of the `Component` of a given type, or `None` if the entity does not
+	/// have one (or does not exist).
+	///
+	/// Unlike `AsyncEntity::wait_for()`, this does not wait for the component to exist.
+	pub async fn get<C: Component + Clone>(&self) -> Option<C> {
+		let id = self.id;
+		self.world
+			.with_world_ref(move |world: &World| world.get::<C>(id).cloned())
+			.await
+	}
+
+	/// Returns the current values of a tuple of `Component`s, or `None` if the entity is missing any
+	/// of them (or does not exist). All of the components are read at the same time.
+	pub async fn get_many<T: CloneComponents>(&self) -> Option<T> {
+		let id = self.id;
+		self.world
+			.with_world_ref(move |world: &World| {
+				let entity = world.get_entity(id).ok()?;
+				T::clone_from_entity(entity)
+			})
+			.await
+	}
+
+	/// Runs the given closure with mutable access to the `Component` of a given type, and returns
+	/// its output. Returns `None` (without running the closure) if the entity does not have one (or
+	/// does not exist).
+	///
+	/// The component is mutated in place within a single command application, so the world cannot
+	/// change in between. Change detection is triggered as normal, but no component hooks or
+	/// observers are run.
+	pub async fn modify<C, T>(&self, f: impl FnOnce(&mut C) -> T + Send + 'static) -> Option<T>
+	where
+		C: Component<Mutability = Mutable>,
+		T: Send + 'static,
+	{
+		let id = self.id;
+		self.world
+			.with_world(move |world: &mut World| {
+				let mut entity = world.get_entity_mut(id).ok()?;
+				let mut component = entity.get_mut::<C>()?;
+				Some(f(&mut component))
+			})
+			.await
+	}
+
+	/// Spawns a new child of the entity with the given `Bundle`, and returns an `AsyncEntity` that
+	/// represents it.
+	///
+	/// Like `AsyncWorld::spawn()`, this does not wait for the child to be spawned.
+	pub async fn spawn_child<B: Bundle>(&self, bundle: B) -> AsyncEntity {
+		self.world.spawn((bundle, ChildOf(self.id))).await
+	}
+
+	/// Adds the given `Entity` as a child of the entity.
+	pub async fn add_child(&self, child: Entity) {
+		self.add_related::<ChildOf>(&[child]).await;
+	}
+
+	/// Sets the parent of the entity to the given `Entity`.
+	pub async fn set_parent(&self, parent: Entity) {
+		self.insert(ChildOf(parent)).await;
+	}
+
+	/// Adds the given entities as related to the entity through the `Relationship` of type `R`.
+	pub async fn add_related<R: Relationship>(&self, related: &[Entity]) {
+		self.world
+			.apply(add_related::<R>(self.id, related.to_vec()))
+			.await;
+	}
+
+	/// Returns the children of the entity, in order. Returns an empty `Vec` if the entity has no
+	/// children (or does not exist).
+	pub async fn children(&self) -> Vec<AsyncEntity> {
+		let id = self.id;
+		let children = self
+			.world
+			.with_world_ref(move |world: &World| {
+				world
+					.get::<Children>(id)
+					.map(|children| children.to_vec())
+					.unwrap_or_default()
+			})
+			.await;
+		children
+			.into_iter()
+			.map(|child| AsyncEntity::new(child, self.world.clone()))
+			.collect()
+	}
+
+	/// Returns the parent of the entity, or `None` if it has no parent (or does not exist).
+	pub async fn parent(&self) -> Option<AsyncEntity> {
+		let id = self.id;
+		let parent = self
+			.world
+			.with_world_ref(move |world: &World| world.get::<ChildOf>(id).map(ChildOf::parent))
+			.await;
+		parent.map(|parent| AsyncEntity::new(parent, self.world.clone()))
+	}
+
 	/// Start waiting for the `Component` of a given type. Returns an `AsyncComponent` which can be further
 	/// waited to receive the value of the component.
 	///
@@ -63,7 +248,12 @@ impl AsyncEntity {
 	pub async fn start_waiting_for<C: Component + Clone>(&self) -> AsyncComponent<C> {
 		let (start_waiting_for, rx) = StartWaitingFor::component(self.id);
 		self.world.apply(start_waiting_for).await;
-		AsyncComponent(rx)
+		AsyncComponent {
+			id: self.id,
+			rx,
+			world: self.world.clone(),
+			_component: PhantomData,
+		}
 	}
 
 	/// Wait for the `Component` of a given type. Returns the value of the component, once it exists
@@ -75,6 +265,150 @@ impl AsyncEntity {
 		self.start_waiting_for().await.wait().await
 	}
 
+	/// Start waiting for the `Component` of a given type, mapping it with the given projection.
+	/// Returns an `AsyncComponent` which can be further waited to receive the projected value.
+	///
+	/// The projection is evaluated inside the Bevy world, so the component does not need to be
+	/// `Clone`.
+	///
+	/// `AsyncEntity::wait_for_map(project).await` is equivalent to
+	/// `AsyncEntity::start_waiting_for_map(project).await.wait().await`.
+	pub async fn start_waiting_for_map<C: Component, T: Send + 'static>(
+		&self,
+		project: impl Fn(&C) -> T + Send + Sync + 'static,
+	) -> AsyncComponent<C, T> {
+		let (start_waiting_for, rx) = StartWaitingFor::component_map(self.id, project);
+		self.world.apply(start_waiting_for).await;
+		AsyncComponent {
+			id: self.id,
+			rx,
+			world: self.world.clone(),
+			_component: PhantomData,
+		}
+	}
+
+	/// Wait for the `Component` of a given type, mapping it with the given projection. Returns the
+	/// projected value, once the component exists on the represented entity.
+	///
+	/// `AsyncEntity::wait_for_map(project).await` is equivalent to
+	/// `AsyncEntity::start_waiting_for_map(project).await.wait().await`.
+	pub async fn wait_for_map<C: Component, T: Send + 'static>(
+		&self,
+		project: impl Fn(&C) -> T + Send + Sync + 'static,
+	) -> T {
+		self.start_waiting_for_map(project).await.wait().await
+	}
+
+	/// Start waiting for the `Component` of a given type to satisfy the given predicate. Returns an
+	/// `AsyncComponent` which can be further waited to receive the value of the component.
+	///
+	/// The predicate is evaluated every frame inside the Bevy world, so no values are sent across
+	/// the channel until it holds.
+	///
+	/// `AsyncEntity::wait_until(predicate).await` is equivalent to
+	/// `AsyncEntity::start_waiting_until(predicate).await.wait().await`.
+	pub async fn start_waiting_until<C: Component + Clone>(
+		&self,
+		predicate: impl Fn(&C) -> bool + Send + Sync + 'static,
+	) -> AsyncComponent<C> {
+		let (start_waiting_for, rx) = StartWaitingFor::component(self.id);
+		self.world.apply(start_waiting_for.until(predicate)).await;
+		AsyncComponent {
+			id: self.id,
+			rx,
+			world: self.world.clone(),
+			_component: PhantomData,
+		}
+	}
+
+	/// Wait for the `Component` of a given type to satisfy the given predicate. Returns the value of
+	/// the component, once it exists on the represented entity and the predicate holds.
+	///
+	/// `AsyncEntity::wait_until(predicate).await` is equivalent to
+	/// `AsyncEntity::start_waiting_until(predicate).await.wait().await`.
+	pub async fn wait_until<C: Component + Clone>(
+		&self,
+		predicate: impl Fn(&C) -> bool + Send + Sync + 'static,
+	) -> C {
+		self.start_waiting_until(predicate).await.wait().await
+	}
+
+	/// Wait for the `Component` of a given type to change (or be inserted). Returns the new value of
+	/// the component, or an error if the entity is despawned first.
+	///
+	/// Unlike `AsyncEntity::wait_for()`, this ignores the current value of the component, and only
+	/// completes once the component is changed after waiting starts.
+	pub async fn wait_for_change<C: Component + Clone>(&self) -> Result<C, EntityDespawned> {
+		let mut changes = pin!(self.changes::<C>().await);
+		changes.next().await.ok_or(EntityDespawned(self.id))
+	}
+
+	/// Start listening for changes to the `Component` of a given type. Returns a `Stream` which
+	/// yields the new value of the component every frame that it is changed (or inserted).
+	///
+	/// The stream ends when the entity is despawned. Dropping the stream stops listening.
+	pub async fn changes<C: Component + Clone>(&self) -> impl Stream<Item = C> + Send + use<C> {
+		let (start_waiting_for, rx) = StartWaitingForChange::new(self.id);
+		self.world.apply(start_waiting_for).await;
+		rx
+	}
+
+	/// Wait for the `Component` of a given type to be removed from the entity (or for the entity to
+	/// be despawned). Completes immediately if the entity does not currently have the component.
+	///
+	/// Every waiter installs its own `Observer`, which runs whenever the component is removed from
+	/// any entity, so the cost of each removal grows with the number of waiters.
+	pub async fn wait_for_removal<C: Component>(&self) {
+		let (start_waiting_for, rx) = StartWaitingForRemoval::<C>::new(self.id);
+		self.world.apply(start_waiting_for).await;
+		recv(rx).await
+	}
+
+	/// Wait for the entity to be despawned. Completes immediately if the entity does not exist.
+	///
+	/// While waiting, the entity carries a private marker component, since entities without any
+	/// components cannot be observed being despawned. Inserting (and later removing) the marker
+	/// moves the entity to another archetype; `AsyncEntity::archetype_components()` leaves it out.
+	/// Every waiter installs its own `Observer`, which runs whenever any marked entity is
+	/// despawned, so the cost of each despawn grows with the number of waiters.
+	pub async fn wait_for_despawn(&self) {
+		let (start_waiting_for, rx) = StartWaitingForDespawn::new(self.id);
+		self.world.apply(start_waiting_for).await;
+		recv(rx).await
+	}
+
+	/// Triggers the `EntityEvent` constructed by `event_fn` for this entity, which will run any
+	/// `Observer`s watching for it (and propagate it, if the event is configured to do so).
+	///
+	/// Like `EntityCommands::trigger()`, this takes a function that constructs the event from the
+	/// entity, such as `Explode::from` or `|entity| Explode(entity)`.
+	pub async fn trigger<'a, T: Default, E: EntityEvent<Trigger<'a> = T>>(
+		&self,
+		event_fn: impl FnOnce(Entity) -> E + Send,
+	) {
+		self.world.trigger(event_fn(self.id)).await;
+	}
+
+	/// Triggers the `EntityEvent` constructed by `event_fn` for this entity using the given
+	/// `Trigger`, which will run any `Observer`s watching for it.
+	pub async fn trigger_with<E: EntityEvent<Trigger<'static>: Send + Sync>>(
+		&self,
+		event_fn: impl FnOnce(Entity) -> E + Send,
+		trigger: E::Trigger<'static>,
+	) {
+		self.world.trigger_with(event_fn(self.id), trigger).await;
+	}
+
+	/// Start observing the `EntityEvent` of a given type when it targets this entity. Returns a
+	/// `Stream` which yields every such event that is triggered.
+	///
+	/// The stream ends when the entity is despawned. Dropping the stream despawns the `Observer`.
+	pub async fn observe<E: EntityEvent + Clone>(&self) -> impl Stream<Item = E> + Send + use<E> {
+		let (start_observing, rx) = StartObserving::new(Some(self.id));
+		self.world.apply(start_observing).await;
+		rx
+	}
+
 	/// Insert the given `Component` of type `I` onto the entity, then immediately wait for a
 	/// component of type `WR` to be added to the entity. After one is received, this will then
 	/// remove the component of type `WR`.
@@ -89,58 +423,159 @@ impl AsyncEntity {
 	}
 }
 
+/// A tuple of `Component`s that can be cloned out of an entity all at once.
+///
+/// This is implemented for tuples of up to 8 `Component + Clone` types, and is used by
+/// `AsyncEntity::get_many()` and `AsyncWorld::query()`.
+pub trait CloneComponents: Send + Sized + 'static {
+	/// The read-only `QueryData` that fetches references to every component.
+	type Query: ReadOnlyQueryData + 'static;
+
+	/// Clones every component out of the given entity, or returns `None` if any are missing.
+	fn clone_from_entity(entity: EntityRef) -> Option<Self>;
+
+	/// Clones every component out of the given query item.
+	fn clone_from_item(item: QueryItem<'_, '_, Self::Query>) -> Self;
+}
+
+macro_rules! impl_clone_components {
+	($($c:ident),*) => {
+		impl<$($c: Component + Clone),*> CloneComponents for ($($c,)*) {
+			type Query = ($(&'static $c,)*);
+
+			fn clone_from_entity(entity: EntityRef) -> Option<Self> {
+				Some(($(entity.get::<$c>()?.clone(),)*))
+			}
+
+			#[allow(non_snake_case)]
+			fn clone_from_item(item: QueryItem<'_, '_, Self::Query>) -> Self {
+				let ($($c,)*) = item;
+				($($c.clone(),)*)
+			}
+		}
+	};
+}
+
+impl_clone_components!(C0);
+impl_clone_components!(C0, C1);
+impl_clone_components!(C0, C1, C2);
+impl_clone_components!(C0, C1, C2, C3);
+impl_clone_components!(C0, C1, C2, C3, C4);
+impl_clone_components!(C0, C1, C2, C3, C4, C5);
+impl_clone_components!(C0, C1, C2, C3, C4, C5, C6);
+impl_clone_components!(C0, C1, C2, C3, C4, C5, C6, C7);
+
 /// Represents a `Component` being retrieved.
 ///
 /// The easiest way to get an `AsyncComponent` is with `AsyncEntity::start_waiting_for()`.
-pub struct AsyncComponent<C: Component>(Receiver<C>);
+/// The received value is of type `T`, which is the component itself unless it was mapped with
+/// `AsyncEntity::start_waiting_for_map()`.
+///
+/// Dropping an `AsyncComponent` cancels the wait;
+/// the corresponding waiter is cleaned up on the next frame.
+pub struct AsyncComponent<C: Component, T = C> {
+	id: Entity,
+	rx: Receiver<T>,
+	world: AsyncWorld,
+	_component: PhantomData<fn() -> C>,
+}
 
-impl<C: Component> fmt::Debug for AsyncComponent<C> {
+impl<C: Component, T> fmt::Debug for AsyncComponent<C, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "AsyncComponent(..)")
 	}
 }
 
-impl<C: Component> AsyncComponent<C> {
+impl<C: Component, T: Send + 'static> AsyncComponent<C, T> {
 	/// Wait for the `Component` to exist, and retrieve its value.
-	pub async fn wait(self) -> C {
-		recv(self.0).await
+	///
+	/// If the entity is despawned first, this will never complete. Use `AsyncComponent::try_wait()`
+	/// to handle that case.
+	pub async fn wait(self) -> T {
+		recv(self.rx).await
+	}
+
+	/// Wait for the `Component` to exist, and retrieve its value. Returns an error if the entity
+	/// is despawned (or the world is dropped) before the component exists.
+	pub async fn try_wait(self) -> Result<T, EntityDespawned> {
+		self.rx.recv().await.map_err(|_| EntityDespawned(self.id))
+	}
+
+	/// Wait for the `Component` to exist, and retrieve its value. Returns `None` if the `Timeout`
+	/// expires (or the entity is despawned) first.
+	pub async fn wait_timeout(self, timeout: impl Into<Timeout>) -> Option<T> {
+		let Self { rx, world, .. } = self;
+		with_timeout(&world, timeout.into(), async move { rx.recv().await.ok() }).await
 	}
 }
 
-pub(crate) struct SpawnAndSendId<B> {
-	bundle: B,
-	sender: Sender<Entity>,
+/// Represents an `Entity` that is owned by the async context, and is despawned when dropped.
+///
+/// This makes sure that transient entities do not leak into the world when the task that spawned
+/// them panics or is cancelled. Use `OwnedAsyncEntity::detach()` to keep the entity alive.
+///
+/// The easiest way to get an `OwnedAsyncEntity` is with `AsyncWorld::spawn_owned()`.
+#[derive(Debug)]
+pub struct OwnedAsyncEntity {
+	entity: AsyncEntity,
+	detached: bool,
 }
 
-impl SpawnAndSendId<()> {
-	pub(crate) fn new_empty() -> (Self, Receiver<Entity>) {
-		let (sender, receiver) = async_channel::bounded(1);
-		(Self { bundle: (), sender }, receiver)
+impl OwnedAsyncEntity {
+	pub(crate) fn new(entity: AsyncEntity) -> Self {
+		Self {
+			entity,
+			detached: false,
+		}
+	}
+
+	/// Converts this `OwnedAsyncEntity` back into a plain `AsyncEntity`, which **WILL NOT**
+	/// despawn the entity when dropped.
+	pub fn detach(mut self) -> AsyncEntity {
+		self.detached = true;
+		AsyncEntity::new(self.entity.id, self.entity.world.clone())
+	}
+
+	/// Recursively despawns the owned entity. See `AsyncEntity::despawn()`.
+	pub async fn despawn(self) {
+		self.detach().despawn().await;
+	}
+
+	/// Recursively despawns the owned entity. Returns an error if the entity does not exist.
+	/// See `AsyncEntity::try_despawn()`.
+	pub async fn try_despawn(self) -> Result<(), AsyncEcsError> {
+		self.detach().try_despawn().await
 	}
 }
 
-impl<B: Bundle> SpawnAndSendId<B> {
-	pub(crate) fn new(bundle: B) -> (Self, Receiver<Entity>) {
-		let (sender, receiver) = async_channel::bounded(1);
-		(Self { bundle, sender }, receiver)
+impl Deref for OwnedAsyncEntity {
+	type Target = AsyncEntity;
+
+	fn deref(&self) -> &Self::Target {
+		&self.entity
 	}
 }
 
-impl<B: Bundle> Command for SpawnAndSendId<B> {
-	fn apply(self, world: &mut World) {
-		let id = world.spawn(self.bundle).id();
-		if let Err(e @ TrySendError::Full(_)) = self.sender.try_send(id) {
-			let _: () = die(e);
+impl Drop for OwnedAsyncEntity {
+	fn drop(&mut self) {
+		// the entity may already be gone, for example if its parent was despawned recursively
+		if !self.detached {
+			self.entity
+				.world
+				.apply_now(despawn_if_exists(self.entity.id));
 		}
 	}
 }
 
 #[cfg(test)]
 mod tests {
+	use crate::AsyncEcsError;
 	use crate::AsyncEcsPlugin;
 	use crate::AsyncWorld;
+	use crate::EntityDespawned;
 	use bevy::prelude::*;
 	use bevy::tasks::AsyncComputeTaskPool;
+	use futures_lite::StreamExt;
 
 	#[derive(Default, Clone, Component)]
 	struct Translation(u8, u8);
@@ -175,6 +610,7 @@ mod tests {
 				Err(_) => app.update(),
 			}
 		};
+		app.update();
 
 		assert!(app.world().get_entity(id).is_ok());
 	}
@@ -200,6 +636,7 @@ mod tests {
 				Err(_) => app.update(),
 			}
 		};
+		app.update();
 
 		let name = app.world().entity(id).get::<Name>().unwrap();
 		assert_eq!("lol", name.as_str());
@@ -228,10 +665,43 @@ mod tests {
 				Err(_) => app.update(),
 			}
 		}
+		app.update();
 
 		assert!(app.world().get_entity(id).is_err());
 	}
 
+	#[test]
+	fn try_despawned() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn_empty().id();
+		app.world_mut().despawn(id);
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entity = async_world.entity(id);
+				let inserted = entity.try_insert(Scale(1, 1)).await;
+				let removed = entity.try_remove::<Scale>().await;
+				let despawned = entity.try_despawn().await;
+				sender.send((inserted, removed, despawned)).await.unwrap();
+			})
+			.detach();
+
+		let (inserted, removed, despawned) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert!(matches!(inserted, Err(AsyncEcsError::EntityNotFound(e)) if e == id));
+		assert!(matches!(removed, Err(AsyncEcsError::EntityNotFound(e)) if e == id));
+		assert!(matches!(despawned, Err(AsyncEcsError::EntityNotFound(e)) if e == id));
+	}
+
 	#[test]
 	fn spawn() {
 		let mut app = App::new();
@@ -258,6 +728,7 @@ mod tests {
 				Err(_) => app.update(),
 			}
 		};
+		app.update();
 
 		let translation = app.world().get::<Translation>(id).unwrap();
 		assert_eq!(2, translation.0);
@@ -329,6 +800,303 @@ mod tests {
 		assert!(app.world().get::<Scale>(id).is_none());
 	}
 
+	#[test]
+	fn get() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app
+			.world_mut()
+			.spawn((Translation(3, 4), Name::new("Frank")))
+			.id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entity = async_world.entity(id);
+				let translation = entity.get::<Translation>().await;
+				let scale = entity.get::<Scale>().await;
+				let many = entity.get_many::<(Translation, Name)>().await;
+				let missing = entity.get_many::<(Translation, Scale)>().await;
+				sender
+					.send((translation, scale, many, missing))
+					.await
+					.unwrap();
+			})
+			.detach();
+
+		let (translation, scale, many, missing) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		let translation = translation.unwrap();
+		assert_eq!((3, 4), (translation.0, translation.1));
+		assert!(scale.is_none());
+		let (translation, name) = many.unwrap();
+		assert_eq!((3, 4), (translation.0, translation.1));
+		assert_eq!("Frank", name.as_str());
+		assert!(missing.is_none());
+	}
+
+	#[derive(Default, Resource)]
+	struct Inserted(u8);
+
+	#[test]
+	fn modify() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.init_resource::<Inserted>();
+		app.world_mut()
+			.register_component_hooks::<Scale>()
+			.on_insert(|mut world, _| world.resource_mut::<Inserted>().0 += 1);
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn(Scale(1, 1)).id();
+		let missing_id = app.world_mut().spawn_empty().id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let previous = async_world
+					.entity(id)
+					.modify(|scale: &mut Scale| {
+						scale.0 += 2;
+						scale.0 - 2
+					})
+					.await;
+				let missing = async_world
+					.entity(missing_id)
+					.modify(|scale: &mut Scale| scale.0)
+					.await;
+				sender.send((previous, missing)).await.unwrap();
+			})
+			.detach();
+
+		let (previous, missing) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(Some(1), previous);
+		assert_eq!(None, missing);
+		assert_eq!(3, app.world().get::<Scale>(id).unwrap().0);
+		assert_eq!(1, app.world().resource::<Inserted>().0);
+	}
+
+	#[test]
+	fn try_wait_despawned() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn_empty().id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entity = async_world.entity(id);
+				let waiting = entity.start_waiting_for::<Scale>().await;
+				entity.despawn().await;
+				sender.send(waiting.try_wait().await).await.unwrap();
+			})
+			.detach();
+
+		let result = loop {
+			match receiver.try_recv() {
+				Ok(result) => break result,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(Err(EntityDespawned(id)), result.map(|scale| scale.0));
+	}
+
+	#[test]
+	fn changes() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (ready_tx, ready_rx) = async_channel::bounded(1);
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn(Scale(0, 0)).id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let changes = async_world.entity(id).changes::<Scale>().await;
+				ready_tx.send(()).await.unwrap();
+				let values: Vec<u8> = changes.take(2).map(|scale| scale.0).collect().await;
+				sender.send(values).await.unwrap();
+			})
+			.detach();
+
+		while ready_rx.try_recv().is_err() {
+			app.update();
+		}
+		app.update();
+		app.update();
+
+		for i in 1..=2 {
+			app.world_mut().get_mut::<Scale>(id).unwrap().0 = i;
+			app.update();
+		}
+
+		let values = loop {
+			match receiver.try_recv() {
+				Ok(values) => break values,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(vec![1, 2], values);
+	}
+
+	#[test]
+	fn wait_for_change_despawned() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn(Scale(0, 0)).id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let result = async_world.entity(id).wait_for_change::<Scale>().await;
+				sender.send(result.map(|scale| scale.0)).await.unwrap();
+			})
+			.detach();
+
+		for _ in 0..3 {
+			app.update();
+		}
+		assert!(receiver.try_recv().is_err());
+		app.world_mut().despawn(id);
+
+		let result = loop {
+			match receiver.try_recv() {
+				Ok(result) => break result,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(Err(EntityDespawned(id)), result);
+	}
+
+	#[test]
+	fn wait_for_removal_and_despawn() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::unbounded();
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let scaled = app.world_mut().spawn(Scale(1, 1)).id();
+		let empty = app.world_mut().spawn_empty().id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				async_world.entity(scaled).wait_for_removal::<Scale>().await;
+				sender.send("removed").await.unwrap();
+				async_world.entity(empty).wait_for_despawn().await;
+				sender.send("despawned").await.unwrap();
+				async_world.entity(empty).wait_for_removal::<Scale>().await;
+				async_world.entity(empty).wait_for_despawn().await;
+				sender.send("done").await.unwrap();
+			})
+			.detach();
+
+		for _ in 0..3 {
+			app.update();
+		}
+		assert!(receiver.try_recv().is_err());
+		app.world_mut().entity_mut(scaled).remove::<Scale>();
+		let signal = loop {
+			match receiver.try_recv() {
+				Ok(signal) => break signal,
+				Err(_) => app.update(),
+			}
+		};
+		assert_eq!("removed", signal);
+
+		for _ in 0..3 {
+			app.update();
+		}
+		assert!(receiver.try_recv().is_err());
+		app.world_mut().despawn(empty);
+		let signals: Vec<_> = std::iter::from_fn(|| {
+			loop {
+				match receiver.try_recv() {
+					Ok(signal) => break Some(signal),
+					Err(_) => app.update(),
+				}
+			}
+		})
+		.take(2)
+		.collect();
+		assert_eq!(vec!["despawned", "done"], signals);
+
+		let world = app.world_mut();
+		let observers = world.query::<&Observer>().iter(world).count();
+		assert_eq!(0, observers);
+	}
+
+	#[test]
+	fn cancelled_wait_for_despawn() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn(Scale(1, 1)).id();
+		let scale_id = app.world().component_id::<Scale>().unwrap();
+		let components = |app: &App| -> Vec<_> {
+			app.world()
+				.inspect_entity(id)
+				.unwrap()
+				.map(|info| info.id())
+				.collect()
+		};
+
+		let entity = async_world.entity(id);
+		let task = AsyncComputeTaskPool::get()
+			.spawn(async move { async_world.entity(id).wait_for_despawn().await });
+		while components(&app).len() == 1 {
+			app.update();
+		}
+
+		// the marker is not reported while waiting
+		let (sender, receiver) = async_channel::bounded(1);
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let components = entity.archetype_components().await;
+				sender.send(components).await.unwrap();
+			})
+			.detach();
+		let reported = loop {
+			match receiver.try_recv() {
+				Ok(components) => break components,
+				Err(_) => app.update(),
+			}
+		};
+		let reported: Vec<_> = reported.into_iter().map(|(id, _)| id).collect();
+		assert_eq!(vec![scale_id], reported);
+
+		drop(task);
+		for _ in 0..3 {
+			app.update();
+		}
+
+		assert_eq!(vec![scale_id], components(&app));
+		let world = app.world_mut();
+		let observers = world.query::<&Observer>().iter(world).count();
+		assert_eq!(0, observers);
+	}
+
 	#[test]
 	fn insert_wait_remove() {
 		let mut app = App::new();
@@ -357,4 +1125,217 @@ mod tests {
 		assert_eq!(7, value.1);
 		assert!(app.world().entity(id).get::<Scale>().is_none());
 	}
+
+	#[derive(Default, Resource)]
+	struct Poked(Vec<Entity>);
+
+	#[derive(EntityEvent)]
+	#[entity_event(propagate)]
+	struct Poke(Entity);
+
+	#[test]
+	fn trigger() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.init_resource::<Poked>()
+			.add_observer(|poke: On<Poke>, mut poked: ResMut<Poked>| {
+				poked.0.push(poke.event_target());
+			});
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let parent = app.world_mut().spawn_empty().id();
+		let child = app.world_mut().spawn(ChildOf(parent)).id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entity = async_world.entity(child);
+				entity.trigger(Poke).await;
+				let mut trigger: <Poke as Event>::Trigger<'static> = Default::default();
+				trigger.propagate = true;
+				entity.trigger_with(Poke, trigger).await;
+				sender.send(()).await.unwrap();
+			})
+			.detach();
+
+		while receiver.try_recv().is_err() {
+			app.update();
+		}
+		app.update();
+
+		assert_eq!(
+			vec![child, child, parent],
+			app.world().resource::<Poked>().0
+		);
+	}
+
+	#[test]
+	fn spawn_owned() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let dropped = async_world.spawn_owned(Scale(1, 1)).await;
+				let detached = async_world.spawn_owned(Scale(2, 2)).await;
+				let despawned = async_world.spawn_owned(Scale(3, 3)).await;
+				let tried = async_world.spawn_owned(Scale(4, 4)).await;
+				let ids = [dropped.id(), detached.id(), despawned.id(), tried.id()];
+				drop(dropped);
+				let _detached = detached.detach();
+				despawned.despawn().await;
+				let result = tried.try_despawn().await;
+				sender.send((ids, result)).await.unwrap();
+			})
+			.detach();
+
+		let ([dropped, detached, despawned, tried], result) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+
+		assert!(app.world().get_entity(dropped).is_err());
+		assert!(app.world().get_entity(detached).is_ok());
+		assert!(app.world().get_entity(despawned).is_err());
+		assert!(app.world().get_entity(tried).is_err());
+		assert!(result.is_ok());
+	}
+
+	#[test]
+	fn spawn_batch() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entities = async_world.spawn_batch((0..100).map(|i| Scale(i, i))).await;
+				let ids: Vec<Entity> = entities.iter().map(|entity| entity.id()).collect();
+				sender.send(ids).await.unwrap();
+			})
+			.detach();
+
+		let ids = loop {
+			match receiver.try_recv() {
+				Ok(ids) => break ids,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+
+		assert_eq!(100, ids.len());
+		for (i, id) in ids.into_iter().enumerate() {
+			assert_eq!(i as u8, app.world().get::<Scale>(id).unwrap().0);
+		}
+	}
+
+	#[test]
+	fn hierarchy() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let parent = async_world.spawn_empty().await;
+				let first = parent.spawn_child(Scale(1, 1)).await;
+				let second = async_world.spawn_empty().await;
+				parent.add_child(second.id()).await;
+				let third = async_world.spawn_empty().await;
+				third.set_parent(parent.id()).await;
+				let fourth = async_world.spawn_empty().await;
+				parent.add_related::<ChildOf>(&[fourth.id()]).await;
+				let grandchild = first.spawn_child(()).await;
+
+				let children: Vec<Entity> = parent
+					.children()
+					.await
+					.into_iter()
+					.map(|child| child.id())
+					.collect();
+				let expected = vec![first.id(), second.id(), third.id(), fourth.id()];
+				let grandparent = grandchild.parent().await.unwrap().parent().await.unwrap();
+				let orphan = parent.parent().await.is_none();
+
+				let ids = (
+					grandparent.id(),
+					grandchild.id(),
+					children,
+					expected,
+					orphan,
+				);
+				parent.despawn().await;
+				sender.send(ids).await.unwrap();
+			})
+			.detach();
+
+		let (parent, grandchild, children, expected, orphan) = loop {
+			match receiver.try_recv() {
+				Ok(ids) => break ids,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+
+		assert_eq!(expected, children);
+		assert!(orphan);
+		// despawning is recursive
+		for id in [parent, grandchild].into_iter().chain(children) {
+			assert!(app.world().get_entity(id).is_err());
+		}
+	}
+
+	#[test]
+	fn presence() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn(Scale(1, 1)).id();
+		let scale_id = app.world().component_id::<Scale>().unwrap();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let entity = async_world.entity(id);
+				let before = (
+					entity.exists().await,
+					entity.has::<Scale>().await,
+					entity.has::<Translation>().await,
+					entity.archetype_components().await,
+				);
+				async_world.entity(id).despawn().await;
+				let after = (
+					entity.exists().await,
+					entity.has::<Scale>().await,
+					entity.has::<Translation>().await,
+					entity.archetype_components().await,
+				);
+				sender.send((before, after)).await.unwrap();
+			})
+			.detach();
+
+		let (before, after) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		let (exists, has_scale, has_translation, components) = before;
+		assert!(exists && has_scale && !has_translation);
+		let ids: Vec<_> = components.into_iter().map(|(id, _)| id).collect();
+		assert_eq!(vec![scale_id], ids);
+		assert_eq!((false, false, false, vec![]), after);
+	}
 }
@@ -0,0 +1,81 @@
+use crate::die;
+use bevy_ecs::error::BevyError;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RegisteredSystemError;
+use bevy_ecs::system::SystemInput;
+use bevy_ecs::system::SystemParamValidationError;
+use bevy_ecs::world::error::EntityMutableFetchError;
+use std::error::Error;
+use std::fmt;
+
+/// The error returned by the fallible `try_*` family of asynchronous operations.
+///
+/// Rather than panicking inside the `Last` schedule, the error is sent back across the channel
+/// to the awaiting task.
+#[derive(Debug)]
+pub enum AsyncEcsError {
+	/// The `Entity` does not exist in the world (it may have been despawned).
+	EntityNotFound(Entity),
+	/// The `Message` of the given type was never registered with the world.
+	MessageNotRegistered(&'static str),
+	/// The `System` is not registered with the world (it may have been unregistered).
+	SystemNotRegistered,
+	/// The `System` did not run because its parameters failed validation.
+	SystemSkipped(SystemParamValidationError),
+	/// The `System` could not be run, or returned an error.
+	SystemFailed(BevyError),
+}
+
+impl fmt::Display for AsyncEcsError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::EntityNotFound(id) => write!(f, "entity {} does not exist", id),
+			Self::MessageNotRegistered(name) => write!(f, "message `{}` is not registered", name),
+			Self::SystemNotRegistered => write!(f, "system is not registered"),
+			Self::SystemSkipped(e) => write!(f, "system did not run: {}", e),
+			Self::SystemFailed(e) => write!(f, "system failed: {}", e),
+		}
+	}
+}
+
+impl Error for AsyncEcsError {}
+
+/// The error returned when an `Entity` is despawned while it is being waited on.
+///
+/// See `AsyncComponent::try_wait()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntityDespawned(pub Entity);
+
+impl fmt::Display for EntityDespawned {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "entity {} was despawned", self.0)
+	}
+}
+
+impl Error for EntityDespawned {}
+
+impl From<EntityDespawned> for AsyncEcsError {
+	fn from(e: EntityDespawned) -> Self {
+		Self::EntityNotFound(e.0)
+	}
+}
+
+impl From<EntityMutableFetchError> for AsyncEcsError {
+	fn from(e: EntityMutableFetchError) -> Self {
+		match e {
+			EntityMutableFetchError::NotSpawned(e) => Self::EntityNotFound(e.entity()),
+			e @ EntityMutableFetchError::AliasedMutability(_) => die(e),
+		}
+	}
+}
+
+impl<I: SystemInput + 'static, O: 'static> From<RegisteredSystemError<I, O>> for AsyncEcsError {
+	fn from(e: RegisteredSystemError<I, O>) -> Self {
+		match e {
+			RegisteredSystemError::SystemIdNotRegistered(_) => Self::SystemNotRegistered,
+			RegisteredSystemError::Skipped(e) => Self::SystemSkipped(e),
+			RegisteredSystemError::Failed(e) => Self::SystemFailed(e),
+			e => Self::SystemFailed(e.into()),
+		}
+	}
+}
@@ -6,16 +6,21 @@
 
 mod command;
 mod entity;
+mod error;
+mod query;
 mod system;
+mod timeout;
 mod util;
 mod wait_for;
 mod world;
 
 use crate::command::receive_and_apply_commands;
+use crate::timeout::drive_timeouts;
 use crate::wait_for::drive_waiting_for;
 use crate::wait_for::initialize_waiters;
 use async_channel::Receiver;
 use bevy_app::prelude::*;
+use bevy_diagnostic::update_frame_count;
 use bevy_ecs::prelude::*;
 use futures_lite::Stream;
 use pin_project_lite::pin_project;
@@ -29,8 +34,16 @@ pub use command::CommandQueueBuilder;
 pub use command::CommandQueueSender;
 pub use entity::AsyncComponent;
 pub use entity::AsyncEntity;
+pub use entity::CloneComponents;
+pub use entity::OwnedAsyncEntity;
+pub use error::AsyncEcsError;
+pub use error::EntityDespawned;
 pub use system::AsyncIOSystem;
+pub use system::AsyncIOSystemGuard;
 pub use system::AsyncSystem;
+pub use system::AsyncSystemGuard;
+pub use timeout::Timeout;
+pub use wait_for::Backpressure;
 pub use world::AsyncMessages;
 pub use world::AsyncResource;
 pub use world::AsyncWorld;
@@ -76,8 +89,17 @@ pub struct AsyncEcsPlugin;
 impl Plugin for AsyncEcsPlugin {
 	fn build(&self, app: &mut App) {
 		app.add_systems(PreStartup, initialize_waiters)
-			.add_systems(Last, (receive_and_apply_commands, ApplyDeferred).chain())
-			.add_systems(PostUpdate, (drive_waiting_for, ApplyDeferred).chain());
+			.add_systems(
+				Last,
+				(receive_and_apply_commands, ApplyDeferred)
+					.chain()
+					// so that frame timeouts are started before the frame count is incremented
+					.before(update_frame_count),
+			)
+			.add_systems(
+				PostUpdate,
+				(drive_waiting_for, ApplyDeferred, drive_timeouts).chain(),
+			);
 	}
 }
 
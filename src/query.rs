@@ -0,0 +1,101 @@
+use crate::die;
+use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryData;
+use bevy_ecs::query::QueryFilter;
+use bevy_ecs::query::ROQueryItem;
+use bevy_platform::collections::HashMap;
+use std::any::Any;
+use std::any::TypeId;
+
+#[derive(Default, Resource)]
+pub(crate) struct QueryCache(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl QueryCache {
+	fn ensure<D: QueryData + 'static, F: QueryFilter + 'static>(
+		&mut self,
+		world: &mut World,
+	) -> &mut QueryState<D, F> {
+		let type_id = TypeId::of::<QueryState<D, F>>();
+		self.0
+			.entry(type_id)
+			.or_insert_with(|| Box::new(QueryState::<D, F>::new(world)))
+			.downcast_mut()
+			.unwrap_or_else(|| die("cached QueryState has the wrong type"))
+	}
+}
+
+pub(crate) fn run_query<D, F, T>(
+	mut f: impl FnMut(ROQueryItem<'_, '_, D>) -> T + Send + 'static,
+) -> impl FnOnce(&mut World) -> Vec<T> + Send + 'static
+where
+	D: QueryData + 'static,
+	F: QueryFilter + 'static,
+{
+	move |world: &mut World| {
+		world.init_resource::<QueryCache>();
+		world.resource_scope(|world, mut cache: Mut<QueryCache>| {
+			let state = cache.ensure::<D, F>(world);
+			state.iter(world).map(&mut f).collect()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::AsyncEcsPlugin;
+	use crate::AsyncWorld;
+	use bevy::prelude::*;
+	use bevy::tasks::AsyncComputeTaskPool;
+
+	#[derive(Clone, Component)]
+	struct Health(u8);
+
+	#[derive(Component)]
+	struct Enemy;
+
+	#[test]
+	fn smoke() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+		let moved = app.world_mut().spawn((Health(1), Enemy)).id();
+		app.world_mut().spawn((Health(2), Enemy));
+		let friend = app.world_mut().spawn((Health(3), Name::new("Friend"))).id();
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let all = async_world.query::<(Health,), ()>().await;
+				let enemies = async_world.query::<(Health,), With<Enemy>>().await;
+				async_world.spawn((Health(4), Enemy)).await;
+				let enemies_again = async_world.query::<(Health,), With<Enemy>>().await;
+				// moving an entity into a new archetype must not hide it from the cached state
+				async_world.entity(moved).insert(Name::new("Moved")).await;
+				let enemies_moved = async_world.query::<(Health,), With<Enemy>>().await;
+				let names = async_world
+					.query_map::<(Entity, &Name), (), _>(|(id, name)| (id, name.to_string()))
+					.await;
+				sender
+					.send((all, enemies, enemies_again, enemies_moved, names))
+					.await
+					.unwrap();
+			})
+			.detach();
+
+		let (all, enemies, enemies_again, enemies_moved, names) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		let sum = |healths: Vec<(Health,)>| healths.iter().map(|(h,)| h.0).sum::<u8>();
+		assert_eq!(6, sum(all));
+		assert_eq!(3, sum(enemies));
+		assert_eq!(7, sum(enemies_again));
+		assert_eq!(7, sum(enemies_moved));
+		assert!(names.contains(&(friend, "Friend".to_string())));
+	}
+}
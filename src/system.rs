@@ -1,13 +1,15 @@
 use crate::die;
-use crate::recv;
+use crate::error::AsyncEcsError;
 use crate::util::remove_system;
 use crate::world::AsyncWorld;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::BoxedSystem;
 use bevy_ecs::system::SystemId;
+use bevy_ecs::system::SystemInput;
 use bevy_platform::sync::Arc;
 use std::any::Any;
 use std::marker::PhantomData;
+use std::ops::Deref;
 
 type BoxedAnySend = Box<dyn Any + Send>;
 type SystemIdWithIO = SystemId<In<BoxedAnySend>, BoxedAnySend>;
@@ -16,7 +18,8 @@ type BoxedSystemWithIO = BoxedSystem<In<BoxedAnySend>, BoxedAnySend>;
 /// Represents a registered `System` that can be run asynchronously.
 ///
 /// Dropping an `AsyncSystem` will not unregister it. Use `AsyncSystem::unregister()`
-/// to clean up an `AsyncSystem` from the main bevy `World`.
+/// to clean up an `AsyncSystem` from the main bevy `World`, or `AsyncSystem::into_guard()`
+/// to unregister it on drop instead.
 ///
 /// The easiest way to get an `AsyncSystem` is with `AsyncWorld::register_system()`.
 #[derive(Debug, Clone)]
@@ -27,14 +30,9 @@ pub struct AsyncSystem {
 
 impl AsyncSystem {
 	pub(crate) async fn new(system: BoxedSystem, world: AsyncWorld) -> Self {
-		let (id_tx, id_rx) = async_channel::bounded(1);
-		world
-			.apply(move |world: &mut World| {
-				let id = world.register_boxed_system(system);
-				id_tx.try_send(id).unwrap_or_else(die);
-			})
+		let id = world
+			.with_world(move |world: &mut World| world.register_boxed_system(system))
 			.await;
-		let id = recv(id_rx).await;
 		let id = Arc::new(id);
 		Self { id, world }
 	}
@@ -49,6 +47,14 @@ impl AsyncSystem {
 			.await;
 	}
 
+	/// Run the system. Returns an error if the system could not be run.
+	pub async fn try_run(&self) -> Result<(), AsyncEcsError> {
+		let id = *self.id;
+		self.world
+			.with_world(move |world: &mut World| Ok(world.run_system(id)?))
+			.await
+	}
+
 	/// Unregister the system.
 	///
 	/// If multiple clones of the AsyncSystem exist, a reference counter will be
@@ -60,13 +66,48 @@ impl AsyncSystem {
 			world.apply(remove_system(id)).await;
 		}
 	}
+
+	/// Converts this `AsyncSystem` into an `AsyncSystemGuard`, which unregisters the system when
+	/// it is dropped.
+	pub fn into_guard(self) -> AsyncSystemGuard {
+		let unregister = UnregisterOnDrop {
+			id: *self.id,
+			world: self.world.clone(),
+		};
+		AsyncSystemGuard {
+			system: self,
+			_unregister: Arc::new(unregister),
+		}
+	}
+}
+
+/// An owned handle to a registered `System`, which unregisters the system when the last clone of
+/// the guard is dropped.
+///
+/// The guard dereferences to the `AsyncSystem` that it was created from. Clones of that inner
+/// `AsyncSystem` do not keep the system registered.
+///
+/// The easiest way to get an `AsyncSystemGuard` is with `AsyncSystem::into_guard()`.
+#[derive(Debug, Clone)]
+pub struct AsyncSystemGuard {
+	system: AsyncSystem,
+	_unregister: Arc<UnregisterOnDrop<(), ()>>,
+}
+
+impl Deref for AsyncSystemGuard {
+	type Target = AsyncSystem;
+
+	fn deref(&self) -> &Self::Target {
+		&self.system
+	}
 }
 
 /// Represents a registered `System` that accepts input and returns output, and can be run
 /// asynchronously.
 ///
 /// Dropping an `AsyncIOSystem` will not unregister it. Use `AsyncSystemIO::unregister()`
-/// to clean up an `AsyncSystemIO` from the main bevy `World`.
+/// to clean up an `AsyncSystemIO` from the main bevy `World`, or `AsyncIOSystem::into_guard()`
+/// to unregister it on drop instead.
 ///
 /// The easiest way to get an `AsyncIOSystem` is with `AsyncWorld::register_io_system()`.
 #[derive(Debug)]
@@ -103,15 +144,9 @@ impl<I: Send + 'static, O: Send + 'static> AsyncIOSystem<I, O> {
 		let system = unbox_input::<I>.pipe(system).pipe(box_output);
 		let system: BoxedSystemWithIO = Box::new(IntoSystem::into_system(system));
 
-		let (id_tx, id_rx) = async_channel::bounded(1);
-		world
-			.apply(move |world: &mut World| {
-				let id = world.register_boxed_system(system);
-				id_tx.try_send(id).unwrap_or_else(die);
-			})
+		let id = world
+			.with_world(move |world: &mut World| world.register_boxed_system(system))
 			.await;
-
-		let id = recv(id_rx).await;
 		let id = Arc::new(id);
 
 		Self {
@@ -123,26 +158,34 @@ impl<I: Send + 'static, O: Send + 'static> AsyncIOSystem<I, O> {
 
 	/// Run the system.
 	pub async fn run(&self, input: I) -> O {
-		let (input_tx, input_rx) = async_channel::bounded(1);
-		let (output_tx, output_rx) = async_channel::bounded(1);
-
 		let input: BoxedAnySend = Box::new(input);
-		input_tx.send(input).await.unwrap_or_else(die);
-
 		let id = *self.id;
-		self.world
-			.apply(move |world: &mut World| {
-				let input = input_rx.try_recv().unwrap_or_else(die);
-				let output = world.run_system_with(id, input).unwrap_or_else(die);
-				output_tx.try_send(output).unwrap_or_else(die);
+		let boxed = self
+			.world
+			.with_world(move |world: &mut World| {
+				world.run_system_with(id, input).unwrap_or_else(die)
 			})
 			.await;
-
-		let boxed: BoxedAnySend = recv(output_rx).await;
 		let concrete = boxed.downcast().unwrap_or_else(die);
 		*concrete
 	}
 
+	/// Run the system. Returns an error if the system could not be run.
+	pub async fn try_run(&self, input: I) -> Result<O, AsyncEcsError> {
+		let input: BoxedAnySend = Box::new(input);
+		let id = *self.id;
+		let boxed = self
+			.world
+			.with_world(move |world: &mut World| {
+				world
+					.run_system_with(id, input)
+					.map_err(AsyncEcsError::from)
+			})
+			.await?;
+		let concrete = boxed.downcast().unwrap_or_else(die);
+		Ok(*concrete)
+	}
+
 	/// Unregister the system.
 	///
 	/// If multiple clones of the AsyncIOSystem exist, a reference counter will be
@@ -154,11 +197,70 @@ impl<I: Send + 'static, O: Send + 'static> AsyncIOSystem<I, O> {
 			world.apply(remove_system(id)).await
 		}
 	}
+
+	/// Converts this `AsyncIOSystem` into an `AsyncIOSystemGuard`, which unregisters the system
+	/// when it is dropped.
+	pub fn into_guard(self) -> AsyncIOSystemGuard<I, O> {
+		let unregister = UnregisterOnDrop {
+			id: *self.id,
+			world: self.world.clone(),
+		};
+		AsyncIOSystemGuard {
+			system: self,
+			_unregister: Arc::new(unregister),
+		}
+	}
+}
+
+/// An owned handle to a registered `System` that accepts input and returns output, which
+/// unregisters the system when the last clone of the guard is dropped.
+///
+/// The guard dereferences to the `AsyncIOSystem` that it was created from. Clones of that inner
+/// `AsyncIOSystem` do not keep the system registered.
+///
+/// The easiest way to get an `AsyncIOSystemGuard` is with `AsyncIOSystem::into_guard()`.
+#[derive(Debug)]
+pub struct AsyncIOSystemGuard<I: Send, O: Send> {
+	system: AsyncIOSystem<I, O>,
+	_unregister: Arc<UnregisterOnDrop<In<BoxedAnySend>, BoxedAnySend>>,
+}
+
+impl<I: Send, O: Send> Clone for AsyncIOSystemGuard<I, O> {
+	fn clone(&self) -> Self {
+		Self {
+			system: self.system.clone(),
+			_unregister: Arc::clone(&self._unregister),
+		}
+	}
+}
+
+impl<I: Send, O: Send> Deref for AsyncIOSystemGuard<I, O> {
+	type Target = AsyncIOSystem<I, O>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.system
+	}
+}
+
+#[derive(Debug)]
+struct UnregisterOnDrop<I: SystemInput + 'static, O: 'static> {
+	id: SystemId<I, O>,
+	world: AsyncWorld,
+}
+
+impl<I: SystemInput + 'static, O: 'static> Drop for UnregisterOnDrop<I, O> {
+	fn drop(&mut self) {
+		self.world.apply_now(remove_system(self.id));
+	}
 }
 
 #[cfg(test)]
 mod tests {
+	use crate::AsyncEcsError;
 	use crate::AsyncEcsPlugin;
+	use crate::AsyncIOSystem;
+	use crate::AsyncSystem;
+	use crate::util::remove_system;
 	use crate::world::AsyncWorld;
 	use bevy::ecs::system::RegisteredSystemError;
 	use bevy::prelude::*;
@@ -337,4 +439,92 @@ mod tests {
 			Err(RegisteredSystemError::SystemIdNotRegistered(_))
 		));
 	}
+
+	#[test]
+	fn try_run_unregistered() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+		let id = app.world_mut().spawn(Counter(4)).id();
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let increase_counter_all = async_world.register_system(increase_counter_all).await;
+				let get_counter_value = async_world.register_io_system(get_counter_value).await;
+
+				let ran = increase_counter_all.try_run().await;
+				let value = get_counter_value.try_run(id).await;
+				async_world
+					.apply(remove_system(*increase_counter_all.id))
+					.await;
+				async_world
+					.apply(remove_system(*get_counter_value.id))
+					.await;
+				let not_ran = increase_counter_all.try_run().await;
+				let no_value = get_counter_value.try_run(id).await;
+				sender.send((ran, value, not_ran, no_value)).await.unwrap();
+			})
+			.detach();
+
+		let (ran, value, not_ran, no_value) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert!(ran.is_ok());
+		assert_eq!(5, value.unwrap());
+		assert!(matches!(not_ran, Err(AsyncEcsError::SystemNotRegistered)));
+		assert!(matches!(no_value, Err(AsyncEcsError::SystemNotRegistered)));
+	}
+
+	#[test]
+	fn guard() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+		let id = app.world_mut().spawn(Counter(4)).id();
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let increase_counter_all = async_world
+					.register_system(increase_counter_all)
+					.await
+					.into_guard();
+				let get_counter_value = async_world
+					.register_io_system(get_counter_value)
+					.await
+					.into_guard();
+				let system = AsyncSystem::clone(&increase_counter_all);
+				let io_system = AsyncIOSystem::clone(&get_counter_value);
+
+				let guard = increase_counter_all.clone();
+				drop(increase_counter_all);
+				guard.run().await;
+				let value = get_counter_value.run(id).await;
+				drop(guard);
+				drop(get_counter_value);
+
+				let not_ran = system.try_run().await;
+				let no_value = io_system.try_run(id).await;
+				sender.send((value, not_ran, no_value)).await.unwrap();
+			})
+			.detach();
+
+		let (value, not_ran, no_value) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(5, value);
+		assert!(matches!(not_ran, Err(AsyncEcsError::SystemNotRegistered)));
+		assert!(matches!(no_value, Err(AsyncEcsError::SystemNotRegistered)));
+	}
 }
@@ -0,0 +1,248 @@
+use crate::die;
+use crate::recv;
+use crate::world::AsyncWorld;
+use async_channel::Receiver;
+use async_channel::Sender;
+use async_channel::TrySendError;
+use bevy_diagnostic::FrameCount;
+use bevy_ecs::prelude::*;
+use bevy_time::Time;
+use futures_lite::future;
+use std::time::Duration;
+
+/// How long to wait before giving up, measured in game time rather than wall-clock time.
+///
+/// A `Timeout` can be constructed directly from a `Duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+	/// Expire after the given number of frames, as counted by the `FrameCount` resource.
+	///
+	/// The timeout starts when its command is applied (in `Last`, before the frame count is
+	/// incremented), and expires in `PostUpdate` of the `n`th frame after that. So
+	/// `Timeout::Frames(1)` expires during the next frame.
+	Frames(u32),
+	/// Expire after the given amount of (virtual) time has elapsed, as measured by the `Time`
+	/// resource. Pausing or slowing down virtual time also pauses or slows down the timeout.
+	Duration(Duration),
+}
+
+impl From<Duration> for Timeout {
+	fn from(duration: Duration) -> Self {
+		Self::Duration(duration)
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Deadline {
+	Frame(u32),
+	Elapsed(Duration),
+}
+
+impl Deadline {
+	fn start(timeout: Timeout, world: &World) -> Option<Self> {
+		match timeout {
+			Timeout::Frames(frames) => {
+				let frame_count = world.get_resource::<FrameCount>()?;
+				Some(Self::Frame(frame_count.0.wrapping_add(frames)))
+			}
+			Timeout::Duration(duration) => {
+				let time = world.get_resource::<Time>()?;
+				Some(Self::Elapsed(time.elapsed() + duration))
+			}
+		}
+	}
+
+	fn expired(&self, frame_count: Option<&FrameCount>, time: Option<&Time>) -> bool {
+		match (self, frame_count, time) {
+			// the frame count wraps, so compare the distance rather than the raw values
+			(Self::Frame(frame), Some(frame_count), _) => {
+				frame_count.0.wrapping_sub(*frame) < u32::MAX / 2
+			}
+			(Self::Elapsed(elapsed), _, Some(time)) => time.elapsed() >= *elapsed,
+			_ => true,
+		}
+	}
+}
+
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub(crate) struct WaitingForTimeout {
+	tx: Sender<()>,
+	deadline: Deadline,
+}
+
+struct StartTimeout {
+	tx: Sender<()>,
+	timeout: Timeout,
+}
+
+impl StartTimeout {
+	fn new(timeout: Timeout) -> (Self, Receiver<()>) {
+		let (tx, rx) = async_channel::bounded(1);
+		(Self { tx, timeout }, rx)
+	}
+}
+
+impl Command for StartTimeout {
+	fn apply(self, world: &mut World) {
+		let Some(deadline) = Deadline::start(self.timeout, world) else {
+			bevy_log::warn!(
+				"cannot start {:?} without the corresponding resource, so it will expire immediately",
+				self.timeout
+			);
+			let _ = self.tx.try_send(());
+			return;
+		};
+		world.spawn((
+			Name::new("WaitingFor(Timeout)"),
+			WaitingForTimeout {
+				tx: self.tx,
+				deadline,
+			},
+		));
+	}
+}
+
+pub(crate) fn drive_timeouts(
+	mut commands: Commands,
+	query: Query<(Entity, &WaitingForTimeout)>,
+	frame_count: Option<Res<FrameCount>>,
+	time: Option<Res<Time>>,
+) {
+	for (id, waiting_for) in query.iter() {
+		if waiting_for.tx.is_closed() {
+			commands.entity(id).despawn();
+			continue;
+		}
+
+		if waiting_for
+			.deadline
+			.expired(frame_count.as_deref(), time.as_deref())
+		{
+			if let Err(e @ TrySendError::Full(_)) = waiting_for.tx.try_send(()) {
+				let _: () = die(e);
+			}
+			commands.entity(id).despawn();
+		}
+	}
+}
+
+/// Races the given future against a `Timeout`, returning `None` if the timeout expires first.
+pub(crate) async fn with_timeout<T: Send>(
+	world: &AsyncWorld,
+	timeout: Timeout,
+	fut: impl Future<Output = Option<T>> + Send,
+) -> Option<T> {
+	let (start_timeout, rx) = StartTimeout::new(timeout);
+	world.apply(start_timeout).await;
+	future::or(fut, async move {
+		recv(rx).await;
+		None
+	})
+	.await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::AsyncEcsPlugin;
+	use bevy::prelude::*;
+	use bevy::tasks::AsyncComputeTaskPool;
+
+	#[derive(Clone, Component)]
+	struct Never;
+
+	#[derive(Clone, Message)]
+	struct Nothing;
+
+	#[derive(Clone, Resource)]
+	struct Present(u8);
+
+	fn count_timeouts(app: &mut App) -> usize {
+		let world = app.world_mut();
+		world.query::<&WaitingForTimeout>().iter(world).count()
+	}
+
+	#[test]
+	fn expired() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.add_message::<Nothing>();
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let id = app.world_mut().spawn_empty().id();
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let component = async_world.entity(id).start_waiting_for::<Never>().await;
+				let component = component.wait_timeout(Timeout::Frames(3)).await;
+				let messages = async_world.start_waiting_for_messages::<Nothing>().await;
+				let message = messages.wait_timeout(Duration::from_millis(1)).await;
+				sender
+					.send((component.is_none(), message.is_none()))
+					.await
+					.unwrap();
+			})
+			.detach();
+
+		let results = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+
+		assert_eq!((true, true), results);
+		assert_eq!(0, count_timeouts(&mut app));
+	}
+
+	#[test]
+	fn frames() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let async_world = AsyncWorld::from_world(app.world_mut());
+		let (start_timeout, rx) = StartTimeout::new(Timeout::Frames(3));
+		pollster::block_on(async_world.apply(start_timeout));
+		app.update();
+
+		let mut frames = 0;
+		while rx.try_recv().is_err() {
+			app.update();
+			frames += 1;
+		}
+		assert_eq!(3, frames);
+	}
+
+	#[test]
+	fn not_expired() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.insert_resource(Present(3));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let resource = async_world.start_waiting_for_resource::<Present>().await;
+				let resource = resource.wait_timeout(Timeout::Frames(1000)).await;
+				sender.send(resource.map(|r| r.0)).await.unwrap();
+			})
+			.detach();
+
+		let result = loop {
+			match receiver.try_recv() {
+				Ok(result) => break result,
+				Err(_) => app.update(),
+			}
+		};
+		app.update();
+		app.update();
+
+		assert_eq!(Some(3), result);
+		assert_eq!(0, count_timeouts(&mut app));
+	}
+}
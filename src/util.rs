@@ -1,5 +1,40 @@
+use crate::die;
+use crate::error::AsyncEcsError;
+use bevy_ecs::bundle::NoBundleEffect;
 use bevy_ecs::prelude::*;
+use bevy_ecs::relationship::Relationship;
 use bevy_ecs::system::SystemId;
+use std::any::type_name;
+
+/// A fallible command, whose result is sent back to the awaiting task.
+pub(crate) trait TryCommand<T>:
+	FnOnce(&mut World) -> Result<T, AsyncEcsError> + Send + 'static
+{
+}
+
+impl<T, F> TryCommand<T> for F where
+	F: FnOnce(&mut World) -> Result<T, AsyncEcsError> + Send + 'static
+{
+}
+
+pub(crate) fn spawn_at<B: Bundle>(id: Entity, bundle: B) -> impl Command {
+	move |world: &mut World| {
+		world.spawn_at(id, bundle).unwrap_or_else(die);
+	}
+}
+
+/// Spawns every reserved entity in the batch, then inserts all of their bundles at once with
+/// `World::insert_batch()`.
+pub(crate) fn spawn_batch_at<B: Bundle<Effect: NoBundleEffect>>(
+	batch: Vec<(Entity, B)>,
+) -> impl Command {
+	move |world: &mut World| {
+		for (id, _) in &batch {
+			world.spawn_empty_at(*id).unwrap_or_else(die);
+		}
+		world.insert_batch(batch);
+	}
+}
 
 pub(crate) fn insert<B: Bundle>(id: Entity, bundle: B) -> impl Command {
 	move |world: &mut World| {
@@ -13,12 +48,25 @@ pub(crate) fn remove<B: Bundle>(id: Entity) -> impl Command {
 	}
 }
 
+pub(crate) fn add_related<R: Relationship>(id: Entity, related: Vec<Entity>) -> impl Command {
+	move |world: &mut World| {
+		world.entity_mut(id).add_related::<R>(&related);
+	}
+}
+
 pub(crate) fn despawn(id: Entity) -> impl Command {
 	move |world: &mut World| {
 		world.despawn(id);
 	}
 }
 
+/// Like `despawn()`, but does not warn if the entity no longer exists.
+pub(crate) fn despawn_if_exists(id: Entity) -> impl Command {
+	move |world: &mut World| {
+		let _ = world.try_despawn(id);
+	}
+}
+
 pub(crate) fn insert_resource<R: Resource>(resource: R) -> impl Command {
 	move |world: &mut World| {
 		world.insert_resource(resource);
@@ -46,3 +94,42 @@ pub(crate) fn trigger_event<'a, T: Default, E: Event<Trigger<'a> = T> + Send + S
 		world.trigger(event);
 	}
 }
+
+pub(crate) fn trigger_event_with<E: Event<Trigger<'static>: Send + Sync>>(
+	event: E,
+	trigger: E::Trigger<'static>,
+) -> impl Command {
+	move |world: &mut World| {
+		world.trigger_with(event, trigger);
+	}
+}
+
+pub(crate) fn try_insert<B: Bundle>(id: Entity, bundle: B) -> impl TryCommand<()> {
+	move |world: &mut World| {
+		world.get_entity_mut(id)?.insert(bundle);
+		Ok(())
+	}
+}
+
+pub(crate) fn try_remove<B: Bundle>(id: Entity) -> impl TryCommand<()> {
+	move |world: &mut World| {
+		world.get_entity_mut(id)?.remove::<B>();
+		Ok(())
+	}
+}
+
+pub(crate) fn try_despawn(id: Entity) -> impl TryCommand<()> {
+	move |world: &mut World| {
+		world.get_entity_mut(id)?.despawn();
+		Ok(())
+	}
+}
+
+pub(crate) fn try_write_message<M: Message>(message: M) -> impl TryCommand<()> {
+	move |world: &mut World| {
+		world
+			.write_message(message)
+			.map(|_| ())
+			.ok_or(AsyncEcsError::MessageNotRegistered(type_name::<M>()))
+	}
+}
@@ -2,13 +2,21 @@ use crate::die;
 use async_channel::Receiver;
 use async_channel::Sender;
 use async_channel::TrySendError;
+use bevy_ecs::change_detection::Tick;
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryEntityError;
 use bevy_ecs::system::BoxedSystem;
 use bevy_ecs::system::IntoSystem;
+use bevy_ecs::system::SystemChangeTick;
 use bevy_ecs::system::SystemId;
+use bevy_platform::cell::SyncCell;
 use bevy_platform::collections::HashMap;
 use bevy_platform::collections::HashSet;
+use bevy_platform::sync::Arc;
+use bevy_platform::sync::atomic::AtomicUsize;
+use bevy_platform::sync::atomic::Ordering;
 use std::any::TypeId;
+use std::collections::VecDeque;
 use std::marker::PhantomData;
 
 #[derive(Default, Debug, Resource)]
@@ -17,23 +25,163 @@ pub struct WaiterCache(HashMap<TypeId, SystemId>);
 #[derive(Default, Debug, Resource)]
 pub struct ActiveWaiters(HashSet<SystemId>);
 
+type Filter<CRE> = Box<dyn Fn(&CRE) -> bool + Send + Sync>;
+type Projection<CRE, T> = Box<dyn Fn(&CRE) -> T + Send + Sync>;
+
+/// What to do when a bounded `AsyncMessages` subscription is full.
+///
+/// Every policy keeps memory use bounded, so every policy may drop messages; the number of dropped
+/// messages is reported by `AsyncMessages::dropped()`.
+///
+/// See `AsyncWorld::start_waiting_for_messages_with()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backpressure {
+	/// Drop the oldest unreceived message to make room for the new one.
+	DropOldest,
+	/// Drop the new message.
+	DropNewest,
+	/// Buffer new messages inside the Bevy world until there is room for them. At most `capacity`
+	/// messages are buffered; once the buffer is full as well, new messages are dropped.
+	Buffer,
+}
+
+struct Bounded<T> {
+	policy: Backpressure,
+	dropped: Arc<AtomicUsize>,
+	// only ever accessed mutably, so the values do not need to be `Sync`
+	backlog: SyncCell<VecDeque<T>>,
+	capacity: usize,
+}
+
+impl<T> Bounded<T> {
+	/// Flushes as much of the backlog as possible, then tries to send the value according to
+	/// the policy.
+	fn send(&mut self, tx: &Sender<T>, value: Option<T>) -> Result<(), TrySendError<T>> {
+		let backlog = self.backlog.get();
+		while let Some(held) = backlog.pop_front() {
+			if let Err(e) = tx.try_send(held) {
+				match e {
+					TrySendError::Full(held) => backlog.push_front(held),
+					e @ TrySendError::Closed(_) => return Err(e),
+				}
+				break;
+			}
+		}
+
+		let Some(value) = value else {
+			return Ok(());
+		};
+
+		match self.policy {
+			Backpressure::DropOldest => match tx.force_send(value) {
+				Ok(Some(_)) => self.drop_one(),
+				Ok(None) => {}
+				Err(e) => return Err(TrySendError::Closed(e.into_inner())),
+			},
+			Backpressure::DropNewest => match tx.try_send(value) {
+				Err(TrySendError::Full(_)) => self.drop_one(),
+				result => return result,
+			},
+			Backpressure::Buffer if !backlog.is_empty() => self.hold_back(value),
+			Backpressure::Buffer => match tx.try_send(value) {
+				Err(TrySendError::Full(value)) => self.hold_back(value),
+				result => return result,
+			},
+		}
+		Ok(())
+	}
+
+	fn hold_back(&mut self, value: T) {
+		let backlog = self.backlog.get();
+		if backlog.len() < self.capacity {
+			backlog.push_back(value);
+		} else {
+			self.drop_one();
+		}
+	}
+
+	fn drop_one(&self) {
+		self.dropped.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+/// Sends a projection of each accepted value back to the awaiting task. This hides the type of
+/// the projection from the systems that process the waiters.
+trait Sink<CRE>: Send + Sync {
+	fn is_closed(&self) -> bool;
+
+	/// Flushes any held back values, then sends a projection of the given value (if any).
+	fn send(&mut self, value: Option<&CRE>) -> Result<(), TrySendError<()>>;
+}
+
+struct Projected<CRE, T> {
+	tx: Sender<T>,
+	project: Projection<CRE, T>,
+	bounded: Option<Bounded<T>>,
+}
+
+impl<CRE: 'static, T: Send + 'static> Projected<CRE, T> {
+	fn boxed(
+		tx: Sender<T>,
+		project: impl Fn(&CRE) -> T + Send + Sync + 'static,
+		bounded: Option<Bounded<T>>,
+	) -> Box<dyn Sink<CRE>> {
+		Box::new(Self {
+			tx,
+			project: Box::new(project),
+			bounded,
+		})
+	}
+}
+
+impl<CRE, T: Send> Sink<CRE> for Projected<CRE, T> {
+	fn is_closed(&self) -> bool {
+		self.tx.is_closed()
+	}
+
+	fn send(&mut self, value: Option<&CRE>) -> Result<(), TrySendError<()>> {
+		let value = value.map(&self.project);
+		let result = match (&mut self.bounded, value) {
+			(Some(bounded), value) => bounded.send(&self.tx, value),
+			(None, Some(value)) => self.tx.try_send(value),
+			(None, None) => Ok(()),
+		};
+		result.map_err(|e| match e {
+			TrySendError::Full(_) => TrySendError::Full(()),
+			TrySendError::Closed(_) => TrySendError::Closed(()),
+		})
+	}
+}
+
 pub(crate) struct StartWaitingFor<CRE> {
-	tx: Sender<CRE>,
+	sink: Box<dyn Sink<CRE>>,
+	filter: Option<Filter<CRE>>,
 	target: Option<Entity>,
 	system: fn() -> BoxedSystem,
 	name: Name,
 }
 
-impl<C: Component + Clone> StartWaitingFor<C> {
+impl<C: Component> StartWaitingFor<C> {
 	fn component_system() -> BoxedSystem {
 		let system = IntoSystem::into_system(process_waiting_components::<C>);
 		Box::new(system)
 	}
 
-	pub(crate) fn component(target: Entity) -> (Self, Receiver<C>) {
+	pub(crate) fn component(target: Entity) -> (Self, Receiver<C>)
+	where
+		C: Clone,
+	{
+		Self::component_map(target, C::clone)
+	}
+
+	pub(crate) fn component_map<T: Send + 'static>(
+		target: Entity,
+		project: impl Fn(&C) -> T + Send + Sync + 'static,
+	) -> (Self, Receiver<T>) {
 		let (tx, rx) = async_channel::bounded(1);
 		let command = Self {
-			tx,
+			sink: Projected::boxed(tx, project, None),
+			filter: None,
 			target: Some(target),
 			system: Self::component_system,
 			name: Name::new("WaitingFor(Component)"),
@@ -42,16 +190,26 @@ impl<C: Component + Clone> StartWaitingFor<C> {
 	}
 }
 
-impl<R: Resource + Clone> StartWaitingFor<R> {
+impl<R: Resource> StartWaitingFor<R> {
 	fn resource_system() -> BoxedSystem {
 		let system = IntoSystem::into_system(process_waiting_resources::<R>);
 		Box::new(system)
 	}
 
-	pub(crate) fn resource() -> (Self, Receiver<R>) {
+	pub(crate) fn resource() -> (Self, Receiver<R>)
+	where
+		R: Clone,
+	{
+		Self::resource_map(R::clone)
+	}
+
+	pub(crate) fn resource_map<T: Send + 'static>(
+		project: impl Fn(&R) -> T + Send + Sync + 'static,
+	) -> (Self, Receiver<T>) {
 		let (tx, rx) = async_channel::bounded(1);
 		let command = Self {
-			tx,
+			sink: Projected::boxed(tx, project, None),
+			filter: None,
 			target: None,
 			system: Self::resource_system,
 			name: Name::new("WaitingFor(Resource)"),
@@ -60,61 +218,145 @@ impl<R: Resource + Clone> StartWaitingFor<R> {
 	}
 }
 
-impl<M: Message + Clone> StartWaitingFor<M> {
+impl<M: Message> StartWaitingFor<M> {
 	fn message_system() -> BoxedSystem {
 		let system = IntoSystem::into_system(process_waiting_messages::<M>);
 		Box::new(system)
 	}
 
-	pub(crate) fn messages() -> (Self, Receiver<M>) {
-		let (tx, rx) = async_channel::unbounded();
-		let command = Self {
-			tx,
+	fn subscription<T: Send + 'static>(
+		tx: Sender<T>,
+		project: impl Fn(&M) -> T + Send + Sync + 'static,
+		bounded: Option<Bounded<T>>,
+	) -> Self {
+		Self {
+			sink: Projected::boxed(tx, project, bounded),
+			filter: None,
 			target: None,
 			system: Self::message_system,
 			name: Name::new("WaitingFor(Messages)"),
+		}
+	}
+
+	pub(crate) fn messages() -> (Self, Receiver<M>)
+	where
+		M: Clone,
+	{
+		Self::messages_map(M::clone)
+	}
+
+	pub(crate) fn messages_map<T: Send + 'static>(
+		project: impl Fn(&M) -> T + Send + Sync + 'static,
+	) -> (Self, Receiver<T>) {
+		let (tx, rx) = async_channel::unbounded();
+		(Self::subscription(tx, project, None), rx)
+	}
+
+	pub(crate) fn messages_with(
+		capacity: usize,
+		policy: Backpressure,
+	) -> (Self, Receiver<M>, Arc<AtomicUsize>)
+	where
+		M: Clone,
+	{
+		let (tx, rx) = async_channel::bounded(capacity);
+		let dropped = Arc::new(AtomicUsize::new(0));
+		let bounded = Bounded {
+			policy,
+			dropped: Arc::clone(&dropped),
+			backlog: SyncCell::new(VecDeque::new()),
+			capacity,
 		};
-		(command, rx)
+		(Self::subscription(tx, M::clone, Some(bounded)), rx, dropped)
 	}
 }
 
-impl<CRE: Send + 'static> StartWaitingFor<CRE> {
-	fn ensure(&self, cache: &mut WaiterCache, world: &mut World) -> SystemId {
-		let type_id = TypeId::of::<CRE>();
-		*cache.0.entry(type_id).or_insert_with(|| {
-			let system = (self.system)();
-			world.register_boxed_system(system)
-		})
+impl<CRE> StartWaitingFor<CRE> {
+	/// Only accept values (or messages) that satisfy the given predicate.
+	pub(crate) fn until(
+		mut self,
+		predicate: impl Fn(&CRE) -> bool + Send + Sync + 'static,
+	) -> Self {
+		self.filter = Some(Box::new(predicate));
+		self
 	}
+}
 
-	fn spawn_waiter(self, world: &mut World) {
-		match self.target {
-			None => world.spawn((self.name, WaitingFor(self.tx))),
-			Some(id) => world.spawn((self.name, WaitingFor(self.tx), Target(id))),
+impl<CRE: Send + Sync + 'static> Command for StartWaitingFor<CRE> {
+	fn apply(self, world: &mut World) {
+		let waiting_for = WaitingFor {
+			sink: self.sink,
+			filter: self.filter,
 		};
+		let waiter = (self.name, waiting_for);
+		match self.target {
+			None => start_waiting::<WaitingFor<CRE>>(world, self.system, waiter),
+			Some(id) => start_waiting::<WaitingFor<CRE>>(world, self.system, (waiter, Target(id))),
+		}
+	}
+}
+
+pub(crate) struct StartWaitingForChange<C> {
+	tx: Sender<C>,
+	target: Entity,
+}
+
+impl<C: Component + Clone> StartWaitingForChange<C> {
+	fn change_system() -> BoxedSystem {
+		let system = IntoSystem::into_system(process_waiting_changes::<C>);
+		Box::new(system)
+	}
+
+	pub(crate) fn new(target: Entity) -> (Self, Receiver<C>) {
+		let (tx, rx) = async_channel::unbounded();
+		(Self { tx, target }, rx)
 	}
 }
 
-impl<CRE: Send + 'static> Command for StartWaitingFor<CRE> {
+impl<C: Component + Clone> Command for StartWaitingForChange<C> {
 	fn apply(self, world: &mut World) {
-		let system_id = world.resource_scope(|world, mut cache| self.ensure(&mut cache, world));
-		world.resource_mut::<ActiveWaiters>().0.insert(system_id);
-		self.spawn_waiter(world);
-		world.run_system(system_id).unwrap_or_else(die);
+		let waiter = (
+			Name::new("WaitingFor(Change)"),
+			WaitingForChange {
+				tx: self.tx,
+				since: world.change_tick(),
+			},
+			Target(self.target),
+		);
+		start_waiting::<WaitingForChange<C>>(world, Self::change_system, waiter);
 	}
 }
 
-struct StopWaitingFor<CRE>(PhantomData<CRE>);
+/// Spawns a waiter, and makes sure that the system that processes waiters of type `W` is
+/// registered and active.
+fn start_waiting<W: Component>(
+	world: &mut World,
+	system: fn() -> BoxedSystem,
+	waiter: impl Bundle,
+) {
+	let type_id = TypeId::of::<W>();
+	let system_id = world.resource_scope(|world, mut cache: Mut<WaiterCache>| {
+		*cache
+			.0
+			.entry(type_id)
+			.or_insert_with(|| world.register_boxed_system(system()))
+	});
+	world.resource_mut::<ActiveWaiters>().0.insert(system_id);
+	world.spawn(waiter);
+	world.run_system(system_id).unwrap_or_else(die);
+}
+
+struct StopWaitingFor<W>(PhantomData<W>);
 
-impl<CRE> Default for StopWaitingFor<CRE> {
+impl<W> Default for StopWaitingFor<W> {
 	fn default() -> Self {
 		Self(PhantomData)
 	}
 }
 
-impl<CRE: Send + 'static> Command for StopWaitingFor<CRE> {
+impl<W: Component> Command for StopWaitingFor<W> {
 	fn apply(self, world: &mut World) {
-		let type_id = TypeId::of::<CRE>();
+		let type_id = TypeId::of::<W>();
 		let system_id = world.resource::<WaiterCache>().0.get(&type_id).copied();
 		if let Some(system_id) = system_id {
 			world.resource_mut::<ActiveWaiters>().0.remove(&system_id);
@@ -122,84 +364,368 @@ impl<CRE: Send + 'static> Command for StopWaitingFor<CRE> {
 	}
 }
 
+pub(crate) struct StartWaitingForRemoval<C> {
+	tx: Sender<()>,
+	target: Entity,
+	_pd: PhantomData<C>,
+}
+
+impl<C: Component> StartWaitingForRemoval<C> {
+	fn removal_system() -> BoxedSystem {
+		let system = IntoSystem::into_system(process_waiting_for_removal::<C>);
+		Box::new(system)
+	}
+
+	pub(crate) fn new(target: Entity) -> (Self, Receiver<()>) {
+		let (tx, rx) = async_channel::bounded(1);
+		let command = Self {
+			tx,
+			target,
+			_pd: PhantomData,
+		};
+		(command, rx)
+	}
+}
+
+impl<C: Component> Command for StartWaitingForRemoval<C> {
+	fn apply(self, world: &mut World) {
+		let Self { tx, target, .. } = self;
+		let has_component = world
+			.get_entity(target)
+			.is_ok_and(|entity| entity.contains::<C>());
+		if !has_component {
+			let _ = tx.try_send(());
+			return;
+		}
+
+		// components are also removed when the entity is despawned, so this covers both cases
+		let observer = Observer::new({
+			let tx = tx.clone();
+			move |remove: On<Remove, C>, mut commands: Commands| {
+				if remove.entity == target {
+					let _ = tx.try_send(());
+					commands.entity(remove.observer()).despawn();
+				}
+			}
+		});
+		let waiter = (
+			Name::new("WaitingFor(Removal)"),
+			observer,
+			WaitingForRemoval::<C>(tx, PhantomData),
+		);
+		start_waiting::<WaitingForRemoval<C>>(world, Self::removal_system, waiter);
+	}
+}
+
+/// Marks an entity that is being waited on by `StartWaitingForDespawn`, since entities without
+/// any components do not trigger any lifecycle events when they are despawned. The marker is
+/// removed again once nothing is waiting for the entity to be despawned.
+#[derive(Component)]
+pub(crate) struct WatchingForDespawn;
+
+pub(crate) struct StartWaitingForDespawn {
+	tx: Sender<()>,
+	target: Entity,
+}
+
+impl StartWaitingForDespawn {
+	fn despawn_system() -> BoxedSystem {
+		let system = IntoSystem::into_system(process_waiting_for_despawn);
+		Box::new(system)
+	}
+
+	pub(crate) fn new(target: Entity) -> (Self, Receiver<()>) {
+		let (tx, rx) = async_channel::bounded(1);
+		(Self { tx, target }, rx)
+	}
+}
+
+impl Command for StartWaitingForDespawn {
+	fn apply(self, world: &mut World) {
+		let Self { tx, target } = self;
+		let Ok(mut entity) = world.get_entity_mut(target) else {
+			let _ = tx.try_send(());
+			return;
+		};
+		entity.insert(WatchingForDespawn);
+
+		// not watching the entity directly, since that would leave an `ObservedBy` behind on it
+		let observer = Observer::new({
+			let tx = tx.clone();
+			move |despawn: On<Despawn, WatchingForDespawn>, mut commands: Commands| {
+				if despawn.entity == target {
+					let _ = tx.try_send(());
+					commands.entity(despawn.observer()).despawn();
+				}
+			}
+		});
+		let waiter = (
+			Name::new("WaitingFor(Despawn)"),
+			observer,
+			WaitingForDespawn(tx),
+			Target(target),
+		);
+		start_waiting::<WaitingForDespawn>(world, Self::despawn_system, waiter);
+	}
+}
+
+pub(crate) struct StartObserving<E> {
+	tx: Sender<E>,
+	target: Option<Entity>,
+}
+
+impl<E: Event + Clone> StartObserving<E> {
+	fn observer_system() -> BoxedSystem {
+		let system = IntoSystem::into_system(process_observing::<E>);
+		Box::new(system)
+	}
+
+	pub(crate) fn new(target: Option<Entity>) -> (Self, Receiver<E>) {
+		let (tx, rx) = async_channel::unbounded();
+		(Self { tx, target }, rx)
+	}
+}
+
+impl<E: Event + Clone> Command for StartObserving<E> {
+	fn apply(self, world: &mut World) {
+		let Self { tx, target } = self;
+		if target.is_some_and(|target| world.get_entity(target).is_err()) {
+			// dropping the sender will close the channel
+			return;
+		}
+
+		let observer = Observer::new({
+			let tx = tx.clone();
+			move |event: On<E>| {
+				let _ = tx.try_send(event.event().clone());
+			}
+		});
+		// the observer is despawned along with the only entity that it watches
+		let observer = match target {
+			Some(target) => observer.with_entity(target),
+			None => observer,
+		};
+		let waiter = (Name::new("WaitingFor(Event)"), observer, Observing(tx));
+		start_waiting::<Observing<E>>(world, Self::observer_system, waiter);
+	}
+}
+
 #[derive(Component)]
 #[component(storage = "SparseSet")]
-struct WaitingFor<CRE>(Sender<CRE>);
+struct WaitingFor<CRE: 'static> {
+	sink: Box<dyn Sink<CRE>>,
+	filter: Option<Filter<CRE>>,
+}
+
+impl<CRE> WaitingFor<CRE> {
+	fn accepts(&self, value: &CRE) -> bool {
+		self.filter.as_ref().is_none_or(|filter| filter(value))
+	}
+}
+
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+struct WaitingForChange<C> {
+	tx: Sender<C>,
+	since: Tick,
+}
 
 #[derive(Component)]
 #[component(storage = "SparseSet")]
 struct Target(Entity);
 
-fn process_waiting_components<C: Component + Clone>(
+#[derive(Component)]
+struct WaitingForRemoval<C>(Sender<()>, PhantomData<fn() -> C>);
+
+#[derive(Component)]
+struct WaitingForDespawn(Sender<()>);
+
+#[derive(Component)]
+struct Observing<E>(Sender<E>);
+
+fn process_waiting_components<C: Component>(
 	mut commands: Commands,
-	query: Query<(Entity, &WaitingFor<C>, &Target)>,
-	components: Query<&C>,
+	mut query: Query<(Entity, &mut WaitingFor<C>, &Target)>,
+	components: Query<Option<&C>>,
 ) {
 	if query.is_empty() {
-		commands.queue(StopWaitingFor::<C>::default());
+		commands.queue(StopWaitingFor::<WaitingFor<C>>::default());
 		return;
 	}
 
-	for (id, waiting_for, target) in query.iter() {
-		if let Ok(component) = components.get(target.0) {
-			if let Err(e @ TrySendError::Full(_)) = waiting_for.0.try_send(component.clone()) {
-				let _: () = die(e);
+	for (id, mut waiting_for, target) in query.iter_mut() {
+		if waiting_for.sink.is_closed() {
+			commands.entity(id).despawn();
+			continue;
+		}
+
+		match components.get(target.0) {
+			Ok(Some(component)) if waiting_for.accepts(component) => {
+				if let Err(e @ TrySendError::Full(_)) = waiting_for.sink.send(Some(component)) {
+					die(e)
+				}
+				commands.entity(id).despawn();
 			}
+			Ok(_) => {}
+			// the target was despawned, dropping the sender will close the channel
+			Err(QueryEntityError::NotSpawned(_)) => commands.entity(id).despawn(),
+			// the target is disabled, so keep waiting until it is enabled again
+			Err(_) => {}
+		}
+	}
+}
+
+fn process_waiting_changes<C: Component + Clone>(
+	mut commands: Commands,
+	mut query: Query<(Entity, &mut WaitingForChange<C>, &Target)>,
+	components: Query<Option<Ref<C>>>,
+	ticks: SystemChangeTick,
+) {
+	if query.is_empty() {
+		commands.queue(StopWaitingFor::<WaitingForChange<C>>::default());
+		return;
+	}
+
+	for (id, mut waiting_for, target) in query.iter_mut() {
+		if waiting_for.tx.is_closed() {
 			commands.entity(id).despawn();
+			continue;
+		}
+
+		match components.get(target.0) {
+			Ok(Some(component)) => {
+				let changed = component.last_changed();
+				if changed.is_newer_than(waiting_for.since, ticks.this_run()) {
+					waiting_for.since = ticks.this_run();
+					if waiting_for.tx.try_send(component.clone()).is_err() {
+						commands.entity(id).despawn();
+					}
+				}
+			}
+			Ok(None) => {}
+			// the target was despawned, dropping the sender will close the channel
+			Err(QueryEntityError::NotSpawned(_)) => commands.entity(id).despawn(),
+			// the target is disabled, so keep waiting until it is enabled again
+			Err(_) => {}
 		}
 	}
 }
 
-fn process_waiting_resources<R: Resource + Clone>(
+fn process_waiting_resources<R: Resource>(
 	mut commands: Commands,
-	query: Query<(Entity, &WaitingFor<R>)>,
+	mut query: Query<(Entity, &mut WaitingFor<R>)>,
 	resource: Option<Res<R>>,
 ) {
 	if query.is_empty() {
-		commands.queue(StopWaitingFor::<R>::default());
+		commands.queue(StopWaitingFor::<WaitingFor<R>>::default());
 		return;
 	}
 
-	for (id, waiting_for) in query.iter() {
-		if let Some(resource) = &resource {
-			if let Err(e @ TrySendError::Full(_)) = waiting_for.0.try_send((*resource).clone()) {
-				let _: () = die(e);
+	for (id, mut waiting_for) in query.iter_mut() {
+		if waiting_for.sink.is_closed() {
+			commands.entity(id).despawn();
+			continue;
+		}
+
+		if let Some(resource) = resource.as_deref().filter(|r| waiting_for.accepts(r)) {
+			if let Err(e @ TrySendError::Full(_)) = waiting_for.sink.send(Some(resource)) {
+				die(e)
 			}
 			commands.entity(id).despawn();
 		}
 	}
 }
 
-fn process_waiting_messages<M: Message + Clone>(
+fn process_waiting_messages<M: Message>(
 	mut commands: Commands,
-	query: Query<(Entity, &WaitingFor<M>)>,
+	mut query: Query<(Entity, &mut WaitingFor<M>)>,
 	mut message_reader: MessageReader<M>,
 ) {
 	if query.is_empty() {
-		commands.queue(StopWaitingFor::<M>::default());
+		commands.queue(StopWaitingFor::<WaitingFor<M>>::default());
 		return;
 	}
 
 	let messages: Vec<&M> = message_reader.read().collect();
-	if messages.is_empty() {
+
+	for (id, mut waiting_for) in query.iter_mut() {
+		if waiting_for.sink.is_closed() {
+			commands.entity(id).despawn();
+			continue;
+		}
+
+		// make room for this frame's messages before sending them
+		let mut result = waiting_for.sink.send(None);
+		for &message in &messages {
+			if result.is_err() {
+				break;
+			}
+			if waiting_for.accepts(message) {
+				result = waiting_for.sink.send(Some(message));
+			}
+		}
+
+		match result {
+			Ok(()) => {}
+			Err(e @ TrySendError::Full(_)) => die(e),
+			Err(TrySendError::Closed(_)) => commands.entity(id).despawn(),
+		}
+	}
+}
+
+fn process_waiting_for_removal<C: Component>(
+	mut commands: Commands,
+	query: Query<(Entity, &WaitingForRemoval<C>)>,
+) {
+	if query.is_empty() {
+		commands.queue(StopWaitingFor::<WaitingForRemoval<C>>::default());
 		return;
 	}
 
 	for (id, waiting_for) in query.iter() {
-		'messages: for &message in &messages {
-			if let Err(e) = waiting_for.0.try_send(message.clone()) {
-				match e {
-					e @ TrySendError::Full(_) => die(e),
-					TrySendError::Closed(_) => {
-						commands.entity(id).despawn();
-						break 'messages;
-					}
-				}
+		if waiting_for.0.is_closed() {
+			commands.entity(id).despawn();
+		}
+	}
+}
+
+fn process_waiting_for_despawn(
+	mut commands: Commands,
+	query: Query<(Entity, &WaitingForDespawn, &Target)>,
+) {
+	if query.is_empty() {
+		commands.queue(StopWaitingFor::<WaitingForDespawn>::default());
+		return;
+	}
+
+	let watched: HashSet<Entity> = query
+		.iter()
+		.filter(|(_, waiting_for, _)| !waiting_for.0.is_closed())
+		.map(|(_, _, target)| target.0)
+		.collect();
+	for (id, waiting_for, target) in query.iter() {
+		if waiting_for.0.is_closed() {
+			commands.entity(id).despawn();
+			if !watched.contains(&target.0) {
+				commands.entity(target.0).try_remove::<WatchingForDespawn>();
 			}
 		}
 	}
 }
 
+fn process_observing<E: Event>(mut commands: Commands, query: Query<(Entity, &Observing<E>)>) {
+	if query.is_empty() {
+		commands.queue(StopWaitingFor::<Observing<E>>::default());
+		return;
+	}
+
+	for (id, observing) in query.iter() {
+		if observing.0.is_closed() {
+			commands.entity(id).despawn();
+		}
+	}
+}
+
 pub(crate) fn drive_waiting_for(mut commands: Commands, waiters: Res<ActiveWaiters>) {
 	for system_id in &waiters.0 {
 		commands.run_system(*system_id);
@@ -215,7 +741,9 @@ pub(crate) fn initialize_waiters(mut commands: Commands) {
 mod tests {
 	use super::*;
 	use bevy::diagnostic::FrameCount;
+	use bevy::ecs::entity_disabling::Disabled;
 	use bevy::prelude::*;
+	use std::cell::Cell;
 
 	#[derive(Clone, Message)]
 	struct MyMessage;
@@ -274,4 +802,361 @@ mod tests {
 			3
 		);
 	}
+
+	#[test]
+	fn disabled_target() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_systems(Update, drive_waiting_for);
+
+		let id = app.world_mut().spawn(Disabled).id();
+		let (start_waiting_for, name_rx) = StartWaitingFor::<Name>::component(id);
+		start_waiting_for.apply(app.world_mut());
+
+		app.world_mut().entity_mut(id).insert(Name::new("Frank"));
+		app.update();
+		assert!(name_rx.try_recv().is_err());
+		assert!(!name_rx.is_closed());
+
+		app.world_mut().entity_mut(id).remove::<Disabled>();
+		app.update();
+		assert_eq!(name_rx.try_recv().unwrap(), Name::new("Frank"));
+	}
+
+	#[test]
+	fn disabled_changes() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_systems(Update, drive_waiting_for);
+
+		let id = app.world_mut().spawn(Name::new("Frank")).id();
+		let (start_waiting_for, name_rx) = StartWaitingForChange::<Name>::new(id);
+		start_waiting_for.apply(app.world_mut());
+		app.update();
+		assert!(name_rx.try_recv().is_err());
+
+		app.world_mut()
+			.entity_mut(id)
+			.insert((Disabled, Name::new("Tim")));
+		app.update();
+		assert!(name_rx.try_recv().is_err());
+		assert!(!name_rx.is_closed());
+
+		app.world_mut().entity_mut(id).remove::<Disabled>();
+		app.update();
+		assert_eq!(name_rx.try_recv().unwrap(), Name::new("Tim"));
+	}
+
+	#[test]
+	fn cancelled() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_message::<MyMessage>()
+			.add_systems(Update, drive_waiting_for);
+
+		#[derive(Clone, Resource)]
+		struct Missing;
+
+		let id = app.world_mut().spawn_empty().id();
+		let (start_waiting_for, name_rx) = StartWaitingFor::<Name>::component(id);
+		start_waiting_for.apply(app.world_mut());
+		let (start_waiting_for, missing_rx) = StartWaitingFor::<Missing>::resource();
+		start_waiting_for.apply(app.world_mut());
+		let (start_waiting_for, messages_rx) = StartWaitingFor::<MyMessage>::messages();
+		start_waiting_for.apply(app.world_mut());
+
+		app.update();
+		assert_eq!(
+			app.world_mut()
+				.query::<(&WaitingFor<Name>, &Target)>()
+				.iter(app.world())
+				.count(),
+			1
+		);
+		assert_eq!(
+			app.world_mut()
+				.query::<&WaitingFor<Missing>>()
+				.iter(app.world())
+				.count(),
+			1
+		);
+		assert_eq!(
+			app.world_mut()
+				.query::<&WaitingFor<MyMessage>>()
+				.iter(app.world())
+				.count(),
+			1
+		);
+
+		drop((name_rx, missing_rx, messages_rx));
+		app.update();
+		app.update();
+
+		assert_eq!(
+			app.world_mut()
+				.query::<&WaitingFor<Name>>()
+				.iter(app.world())
+				.count(),
+			0
+		);
+		assert_eq!(
+			app.world_mut()
+				.query::<&WaitingFor<Missing>>()
+				.iter(app.world())
+				.count(),
+			0
+		);
+		assert_eq!(
+			app.world_mut()
+				.query::<&WaitingFor<MyMessage>>()
+				.iter(app.world())
+				.count(),
+			0
+		);
+		assert!(app.world().resource::<ActiveWaiters>().0.is_empty());
+	}
+
+	#[test]
+	fn cancelled_removal() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_systems(Update, drive_waiting_for);
+
+		let id = app.world_mut().spawn(Name::new("removable")).id();
+		let (start_waiting_for, rx) = StartWaitingForRemoval::<Name>::new(id);
+		start_waiting_for.apply(app.world_mut());
+
+		app.update();
+		assert_eq!(
+			app.world_mut()
+				.query::<(&WaitingForRemoval<Name>, &Observer)>()
+				.iter(app.world())
+				.count(),
+			1
+		);
+
+		drop(rx);
+		app.update();
+		app.update();
+
+		assert_eq!(
+			app.world_mut()
+				.query::<&Observer>()
+				.iter(app.world())
+				.count(),
+			0
+		);
+		assert!(app.world().resource::<ActiveWaiters>().0.is_empty());
+		assert_eq!(1, app.world().inspect_entity(id).unwrap().count());
+	}
+
+	#[test]
+	fn until() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_systems(Update, drive_waiting_for);
+
+		let id = app.world_mut().spawn(Name::new("Tim")).id();
+		let (start_waiting_for, name_rx) = StartWaitingFor::<Name>::component(id);
+		start_waiting_for
+			.until(|name| name.as_str() == "Frank")
+			.apply(app.world_mut());
+		let (start_waiting_for, frame_rx) = StartWaitingFor::<FrameCount>::resource();
+		start_waiting_for
+			.until(|frame_count| frame_count.0 >= 3)
+			.apply(app.world_mut());
+
+		app.update();
+		assert!(name_rx.try_recv().is_err());
+		assert!(frame_rx.try_recv().is_err());
+
+		app.world_mut().entity_mut(id).insert(Name::new("Frank"));
+		app.update();
+		app.update();
+		app.update();
+		assert_eq!(name_rx.try_recv().unwrap(), Name::new("Frank"));
+		assert_eq!(frame_rx.try_recv().unwrap().0, 3);
+	}
+
+	#[derive(Clone, Message)]
+	struct Numbered(u8);
+
+	#[test]
+	fn backpressure() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_message::<Numbered>()
+			.add_systems(Update, drive_waiting_for);
+
+		let mut subscribe = |policy| {
+			let (start_waiting_for, rx, dropped) =
+				StartWaitingFor::<Numbered>::messages_with(2, policy);
+			start_waiting_for.apply(app.world_mut());
+			(rx, dropped)
+		};
+		let (oldest_rx, oldest_dropped) = subscribe(Backpressure::DropOldest);
+		let (newest_rx, newest_dropped) = subscribe(Backpressure::DropNewest);
+		let (buffer_rx, buffer_dropped) = subscribe(Backpressure::Buffer);
+
+		for i in 0..6 {
+			app.world_mut().write_message(Numbered(i));
+		}
+		app.update();
+
+		let drain = |rx: &Receiver<Numbered>| -> Vec<u8> {
+			std::iter::from_fn(|| rx.try_recv().ok().map(|n| n.0)).collect()
+		};
+		assert_eq!(vec![4, 5], drain(&oldest_rx));
+		assert_eq!(4, oldest_dropped.load(Ordering::Relaxed));
+		assert_eq!(vec![0, 1], drain(&newest_rx));
+		assert_eq!(4, newest_dropped.load(Ordering::Relaxed));
+		// the backlog holds back at most `capacity` messages, and the rest are dropped
+		assert_eq!(vec![0, 1], drain(&buffer_rx));
+		assert_eq!(2, buffer_dropped.load(Ordering::Relaxed));
+
+		app.world_mut().write_message(Numbered(6));
+		app.update();
+		assert_eq!(vec![2, 3], drain(&buffer_rx));
+		app.update();
+		assert_eq!(vec![6], drain(&buffer_rx));
+		assert_eq!(2, buffer_dropped.load(Ordering::Relaxed));
+	}
+
+	#[test]
+	fn filtered_messages() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_message::<Numbered>()
+			.add_systems(Update, drive_waiting_for);
+
+		let (start_waiting_for, even_rx) = StartWaitingFor::<Numbered>::messages();
+		start_waiting_for
+			.until(|n| n.0 % 2 == 0)
+			.apply(app.world_mut());
+
+		for i in 0..5 {
+			app.world_mut().write_message(Numbered(i));
+		}
+		app.update();
+
+		let even: Vec<u8> = std::iter::from_fn(|| even_rx.try_recv().ok().map(|n| n.0)).collect();
+		assert_eq!(vec![0, 2, 4], even);
+	}
+
+	/// Deliberately not `Clone`.
+	#[derive(Component)]
+	struct Buffer(Vec<u8>);
+
+	/// Deliberately not `Clone`.
+	#[derive(Message)]
+	struct Packet(Vec<u8>);
+
+	/// Deliberately not `Clone`.
+	#[derive(Resource)]
+	struct Inventory(Vec<u8>);
+
+	#[test]
+	fn mapped() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_message::<Packet>()
+			.add_systems(Update, drive_waiting_for);
+
+		let id = app.world_mut().spawn_empty().id();
+		// the projected value does not need to be `Sync`
+		let (start_waiting_for, len_rx) =
+			StartWaitingFor::<Buffer>::component_map(id, |b| Cell::new(b.0.len()));
+		start_waiting_for.apply(app.world_mut());
+		app.update();
+		assert!(len_rx.try_recv().is_err());
+
+		app.world_mut().entity_mut(id).insert(Buffer(vec![1, 2, 3]));
+		app.update();
+		assert_eq!(len_rx.try_recv().unwrap().get(), 3);
+
+		let (start_waiting_for, sum_rx) =
+			StartWaitingFor::<Packet>::messages_map(|b| b.0.iter().sum::<u8>());
+		start_waiting_for
+			.until(|b| !b.0.is_empty())
+			.apply(app.world_mut());
+
+		app.world_mut().write_message(Packet(vec![1, 2]));
+		app.world_mut().write_message(Packet(vec![]));
+		app.world_mut().write_message(Packet(vec![3, 4]));
+		app.update();
+
+		let sums: Vec<u8> = std::iter::from_fn(|| sum_rx.try_recv().ok()).collect();
+		assert_eq!(vec![3, 7], sums);
+
+		let (start_waiting_for, count_rx) =
+			StartWaitingFor::<Inventory>::resource_map(|i| i.0.len());
+		start_waiting_for.apply(app.world_mut());
+		app.update();
+		assert!(count_rx.try_recv().is_err());
+
+		app.world_mut().insert_resource(Inventory(vec![1, 2]));
+		app.update();
+		assert_eq!(count_rx.try_recv().unwrap(), 2);
+	}
+
+	#[derive(Clone, Event)]
+	struct Ping(u8);
+
+	#[derive(Clone, EntityEvent)]
+	struct Poke(Entity);
+
+	#[test]
+	fn observing() {
+		let mut app = App::new();
+		app.add_plugins(MinimalPlugins)
+			.init_resource::<WaiterCache>()
+			.init_resource::<ActiveWaiters>()
+			.add_systems(Update, drive_waiting_for);
+
+		let (start_observing, ping_rx) = StartObserving::<Ping>::new(None);
+		start_observing.apply(app.world_mut());
+		app.world_mut().trigger(Ping(1));
+		app.world_mut().trigger(Ping(2));
+		assert_eq!(ping_rx.try_recv().unwrap().0, 1);
+		assert_eq!(ping_rx.try_recv().unwrap().0, 2);
+
+		let id = app.world_mut().spawn_empty().id();
+		let other = app.world_mut().spawn_empty().id();
+		let (start_observing, poke_rx) = StartObserving::<Poke>::new(Some(id));
+		start_observing.apply(app.world_mut());
+		app.world_mut().trigger(Poke(other));
+		app.world_mut().trigger(Poke(id));
+		assert_eq!(poke_rx.try_recv().unwrap().0, id);
+		assert!(poke_rx.try_recv().is_err());
+
+		// despawning the target ends the stream
+		app.world_mut().despawn(id);
+		app.update();
+		assert!(poke_rx.is_closed());
+
+		// dropping the stream despawns the observer
+		drop(ping_rx);
+		app.update();
+		app.update();
+		let world = app.world_mut();
+		let observing = world.query::<&Observing<Ping>>().iter(world).count();
+		assert_eq!(0, observing);
+		assert!(world.resource::<ActiveWaiters>().0.is_empty());
+	}
 }
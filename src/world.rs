@@ -1,29 +1,55 @@
 use crate::CowStr;
+use crate::command;
 use crate::command::BoxedCommand;
 use crate::command::CommandQueueBuilder;
-use crate::command::CommandQueueReceiver;
 use crate::command::CommandQueueSender;
 use crate::die;
 use crate::entity::AsyncEntity;
-use crate::entity::SpawnAndSendId;
+use crate::entity::CloneComponents;
+use crate::entity::OwnedAsyncEntity;
+use crate::error::AsyncEcsError;
+use crate::query::run_query;
 use crate::recv;
 use crate::system::AsyncIOSystem;
 use crate::system::AsyncSystem;
+use crate::timeout::Timeout;
+use crate::timeout::with_timeout;
 use crate::util::insert_resource;
 use crate::util::remove_resource;
+use crate::util::spawn_at;
+use crate::util::spawn_batch_at;
 use crate::util::trigger_event;
+use crate::util::trigger_event_with;
+use crate::util::try_write_message;
+use crate::wait_for::Backpressure;
+use crate::wait_for::StartObserving;
 use crate::wait_for::StartWaitingFor;
 use async_channel::Receiver;
+use async_channel::TrySendError;
+use bevy_ecs::bundle::NoBundleEffect;
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryData;
+use bevy_ecs::query::QueryFilter;
+use bevy_ecs::query::ROQueryItem;
+use bevy_ecs::system::RunSystemError;
 use bevy_ecs::system::RunSystemOnce;
+use bevy_platform::sync::Arc;
+use bevy_platform::sync::atomic::AtomicUsize;
+use bevy_platform::sync::atomic::Ordering;
+use futures_lite::Stream;
 use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
 
 /// Exposes asynchronous access to the Bevy ECS `World`.
 ///
 /// The easiest way to get an `AsyncWorld` is with `AsyncWorld::from_world()`.
 ///
 /// ## Commands
-/// Apply any `Command` asynchronously with `AsyncWorld::apply_command`.
+/// Apply any `Command` asynchronously with `AsyncWorld::apply`, or run a closure and receive
+/// its output with `AsyncWorld::with_world`.
 ///
 /// ## Systems
 /// Just like their synchronous variants, asynchronous `System`s must be registered
@@ -31,10 +57,10 @@ use std::fmt;
 /// if they are registered with `AsyncWorld::register_io_system`.
 ///
 /// ## Entities
-/// Spawn entities with the `AsyncWorld::spawn_*` family.
+/// Spawn entities with the `AsyncWorld::spawn_*` family, and query them with `AsyncWorld::query`.
 ///
 /// ## Resources
-/// Insert, remove, and wait for resources to exist.
+/// Insert, remove, read, modify, and wait for resources to exist.
 #[derive(Clone, Debug)]
 pub struct AsyncWorld(CommandQueueSender);
 
@@ -49,6 +75,41 @@ impl AsyncWorld {
 		self.0.send_single(BoxedCommand::new(command)).await
 	}
 
+	/// Applies the given `Command` to the world without waiting. This is meant for `Drop`
+	/// implementations, where `.await` is not available.
+	pub(crate) fn apply_now<C: Command>(&self, command: C) {
+		self.0.try_send_queue(BoxedCommand::new(command).into());
+	}
+
+	/// Runs the given closure with exclusive access to the world, and returns its output.
+	///
+	/// The closure is run during command application in the `Last` schedule, just like any other
+	/// `Command` applied with `AsyncWorld::apply()`.
+	pub async fn with_world<T: Send + 'static>(
+		&self,
+		f: impl FnOnce(&mut World) -> T + Send + 'static,
+	) -> T {
+		let (tx, rx) = async_channel::bounded(1);
+		self.apply(move |world: &mut World| {
+			if let Err(e @ TrySendError::Full(_)) = tx.try_send(f(world)) {
+				let _: () = die(e);
+			}
+		})
+		.await;
+		recv(rx).await
+	}
+
+	/// Runs the given closure with read-only access to the world, and returns its output.
+	///
+	/// `AsyncWorld::with_world_ref(f).await` is equivalent to
+	/// `AsyncWorld::with_world(|world| f(world)).await`.
+	pub async fn with_world_ref<T: Send + 'static>(
+		&self,
+		f: impl FnOnce(&World) -> T + Send + 'static,
+	) -> T {
+		self.with_world(move |world: &mut World| f(world)).await
+	}
+
 	/// Starts building a `CommandQueue`.
 	pub fn start_queue(&self) -> CommandQueueBuilder {
 		CommandQueueBuilder::new(self.sender())
@@ -62,6 +123,17 @@ impl AsyncWorld {
 		.await
 	}
 
+	/// Run a [`System`] once, supplying an input value and receiving an output value. Returns an
+	/// error if the system could not be run.
+	pub async fn run_system_once_with<I: Send + 'static, O: Send + 'static, M>(
+		&self,
+		system: impl IntoSystem<In<I>, O, M> + Send + 'static,
+		input: I,
+	) -> Result<O, RunSystemError> {
+		self.with_world(move |world: &mut World| world.run_system_once_with(system, input))
+			.await
+	}
+
 	/// Registers a `System` and returns an `AsyncSystem` that can be used to run the system on demand.
 	pub async fn register_system<M>(
 		&self,
@@ -80,30 +152,109 @@ impl AsyncWorld {
 		AsyncIOSystem::new(system, self.clone()).await
 	}
 
+	/// Runs a `Query` for a tuple of `Component`s and returns a snapshot of every match, cloned.
+	///
+	/// Unlike a `Query` system parameter, `T` is not arbitrary `QueryData`: it must be a tuple of up
+	/// to 8 owned `Component + Clone` types (see `CloneComponents`). For anything else, such as
+	/// `Entity`, `Option<&C>` or `Has<C>`, use `AsyncWorld::query_map()`.
+	///
+	/// The `QueryState` is cached between calls, just like a `Query` system parameter.
+	pub async fn query<T: CloneComponents, F: QueryFilter + 'static>(&self) -> Vec<T> {
+		self.query_map::<T::Query, F, T>(T::clone_from_item).await
+	}
+
+	/// Runs a `Query` and returns a snapshot of every match, mapped through the given projection.
+	///
+	/// The `QueryState` is cached between calls, just like a `Query` system parameter.
+	pub async fn query_map<D, F, T>(
+		&self,
+		f: impl FnMut(ROQueryItem<'_, '_, D>) -> T + Send + 'static,
+	) -> Vec<T>
+	where
+		D: QueryData + 'static,
+		F: QueryFilter + 'static,
+		T: Send + 'static,
+	{
+		self.with_world(run_query::<D, F, T>(f)).await
+	}
+
 	/// Constructs an `AsyncEntity` for the given `Entity`. If the entity does not exist, any operation
-	/// performed on it will panic.
+	/// performed on it will panic. Use `AsyncEntity::exists()` or the `try_*` family to handle that
+	/// case.
 	pub fn entity(&self, id: Entity) -> AsyncEntity {
 		AsyncEntity::new(id, self.clone())
 	}
 
+	/// Reserves an `Entity` id, which can be spawned later with `World::spawn_at()` (for example,
+	/// as part of a `CommandQueue` that spawns and configures a whole tree of entities at once).
+	///
+	/// A pool of ids is reserved ahead of time and refilled every frame, so this only waits if the
+	/// pool has run out. The pool starts with 16 ids, and whenever more than half of it is taken
+	/// within a frame, it grows to twice the number of ids taken, so that repeated bursts of
+	/// spawning wait less and less often. Until it is spawned, the entity does not exist in the
+	/// world.
+	pub async fn reserve_entity(&self) -> Entity {
+		self.0.reserve_entity().await
+	}
+
+	/// Reserves an `Entity` id without waiting, or returns `None` if the pool of ids reserved ahead
+	/// of time has run out. See `AsyncWorld::reserve_entity()`.
+	pub fn try_reserve_entity(&self) -> Option<Entity> {
+		self.0.try_reserve_entity()
+	}
+
 	/// Spawns a new `Entity` and returns an `AsyncEntity` that represents it, which can be used
 	/// to further manipulate the entity.
+	///
+	/// The entity id is taken from the reserved pool (see `AsyncWorld::reserve_entity()`), so this
+	/// does not wait for the entity to be spawned. Operations on the returned `AsyncEntity` are
+	/// applied after the entity is spawned.
 	pub async fn spawn_empty(&self) -> AsyncEntity {
-		let (command, receiver) = SpawnAndSendId::new_empty();
-		self.apply(command).await;
-		let id = recv(receiver).await;
-		AsyncEntity::new(id, self.clone())
+		self.spawn(()).await
 	}
 
 	/// Spawns a new `Entity` with the given `Bundle` and returns an `AsyncEntity` that represents it,
 	/// which can be used to further manipulate the entity.
+	///
+	/// The entity id is taken from the reserved pool (see `AsyncWorld::reserve_entity()`), so this
+	/// does not wait for the entity to be spawned. Operations on the returned `AsyncEntity` are
+	/// applied after the entity is spawned.
 	pub async fn spawn<B: Bundle>(&self, bundle: B) -> AsyncEntity {
-		let (command, receiver) = SpawnAndSendId::new(bundle);
-		self.apply(command).await;
-		let id = recv(receiver).await;
+		let id = self.reserve_entity().await;
+		self.apply(spawn_at(id, bundle)).await;
 		AsyncEntity::new(id, self.clone())
 	}
 
+	/// Spawns a batch of new entities with the given `Bundle`s and returns `AsyncEntity`s that
+	/// represent them, in the same order.
+	///
+	/// Like `AsyncWorld::spawn()`, the entity ids are taken from the reserved pool, so this does
+	/// not wait for the entities to be spawned. All the entities are spawned in a single `Command`,
+	/// and their components are inserted with `World::insert_batch()`, which is much faster than
+	/// spawning them one by one.
+	pub async fn spawn_batch<B: Bundle<Effect: NoBundleEffect>>(
+		&self,
+		bundles: impl IntoIterator<Item = B> + Send,
+	) -> Vec<AsyncEntity> {
+		let bundles: Vec<B> = bundles.into_iter().collect();
+		let mut batch = Vec::with_capacity(bundles.len());
+		for bundle in bundles {
+			batch.push((self.reserve_entity().await, bundle));
+		}
+		let entities = batch
+			.iter()
+			.map(|(id, _)| AsyncEntity::new(*id, self.clone()))
+			.collect();
+		self.apply(spawn_batch_at(batch)).await;
+		entities
+	}
+
+	/// Spawns a new `Entity` with the given `Bundle` and returns an `OwnedAsyncEntity` that
+	/// represents it, which despawns the entity when dropped.
+	pub async fn spawn_owned<B: Bundle>(&self, bundle: B) -> OwnedAsyncEntity {
+		OwnedAsyncEntity::new(self.spawn(bundle).await)
+	}
+
 	/// Spawns a new `Entity` and returns an `AsyncEntity` that represents it, which can be used
 	/// to further manipulate the entity. This function attaches a bevy `Name` component with the given
 	/// value.
@@ -121,6 +272,30 @@ impl AsyncWorld {
 		self.apply(remove_resource::<R>()).await;
 	}
 
+	/// Returns the current value of the `Resource` of a given type, or `None` if it does not exist.
+	///
+	/// Unlike `AsyncWorld::wait_for_resource()`, this does not wait for the resource to exist.
+	pub async fn get_resource<R: Resource + Clone>(&self) -> Option<R> {
+		self.with_world_ref(|world: &World| world.get_resource::<R>().cloned())
+			.await
+	}
+
+	/// Runs the given closure with mutable access to the `Resource` of a given type, and returns
+	/// its output. Returns `None` (without running the closure) if the resource does not exist.
+	///
+	/// The resource is read, modified and written back within a single command application, so the
+	/// world cannot change in between.
+	pub async fn resource_scope<R: Resource, T: Send + 'static>(
+		&self,
+		f: impl FnOnce(&mut R) -> T + Send + 'static,
+	) -> Option<T> {
+		self.with_world(move |world: &mut World| {
+			let mut resource = world.get_resource_mut::<R>()?;
+			Some(f(&mut resource))
+		})
+		.await
+	}
+
 	/// Start waiting for the `Resource` of a given type. Returns an `AsyncResource` which can be further
 	/// waited to receive the value of the resource.
 	///
@@ -129,7 +304,11 @@ impl AsyncWorld {
 	pub async fn start_waiting_for_resource<R: Resource + Clone>(&self) -> AsyncResource<R> {
 		let (start_waiting_for, rx) = StartWaitingFor::resource();
 		self.apply(start_waiting_for).await;
-		AsyncResource(rx)
+		AsyncResource {
+			rx,
+			world: self.clone(),
+			_resource: PhantomData,
+		}
 	}
 
 	/// Wait for the `Resource` of a given type. Returns the value of the resource, once it exists.
@@ -140,11 +319,88 @@ impl AsyncWorld {
 		self.start_waiting_for_resource().await.wait().await
 	}
 
+	/// Start waiting for the `Resource` of a given type, mapping it with the given projection.
+	/// Returns an `AsyncResource` which can be further waited to receive the projected value.
+	///
+	/// The projection is evaluated inside the Bevy world, so the resource does not need to be
+	/// `Clone`.
+	///
+	/// `AsyncWorld::wait_for_resource_map(project).await` is equivalent to
+	/// `AsyncWorld::start_waiting_for_resource_map(project).await.wait().await`.
+	pub async fn start_waiting_for_resource_map<R: Resource, T: Send + 'static>(
+		&self,
+		project: impl Fn(&R) -> T + Send + Sync + 'static,
+	) -> AsyncResource<R, T> {
+		let (start_waiting_for, rx) = StartWaitingFor::resource_map(project);
+		self.apply(start_waiting_for).await;
+		AsyncResource {
+			rx,
+			world: self.clone(),
+			_resource: PhantomData,
+		}
+	}
+
+	/// Wait for the `Resource` of a given type, mapping it with the given projection. Returns the
+	/// projected value, once the resource exists.
+	///
+	/// `AsyncWorld::wait_for_resource_map(project).await` is equivalent to
+	/// `AsyncWorld::start_waiting_for_resource_map(project).await.wait().await`.
+	pub async fn wait_for_resource_map<R: Resource, T: Send + 'static>(
+		&self,
+		project: impl Fn(&R) -> T + Send + Sync + 'static,
+	) -> T {
+		self.start_waiting_for_resource_map(project)
+			.await
+			.wait()
+			.await
+	}
+
+	/// Start waiting for the `Resource` of a given type to satisfy the given predicate. Returns an
+	/// `AsyncResource` which can be further waited to receive the value of the resource.
+	///
+	/// The predicate is evaluated every frame inside the Bevy world, so no values are sent across
+	/// the channel until it holds.
+	///
+	/// `AsyncWorld::wait_until_resource(predicate).await` is equivalent to
+	/// `AsyncWorld::start_waiting_until_resource(predicate).await.wait().await`.
+	pub async fn start_waiting_until_resource<R: Resource + Clone>(
+		&self,
+		predicate: impl Fn(&R) -> bool + Send + Sync + 'static,
+	) -> AsyncResource<R> {
+		let (start_waiting_for, rx) = StartWaitingFor::resource();
+		self.apply(start_waiting_for.until(predicate)).await;
+		AsyncResource {
+			rx,
+			world: self.clone(),
+			_resource: PhantomData,
+		}
+	}
+
+	/// Wait for the `Resource` of a given type to satisfy the given predicate. Returns the value of
+	/// the resource, once it exists and the predicate holds.
+	///
+	/// `AsyncWorld::wait_until_resource(predicate).await` is equivalent to
+	/// `AsyncWorld::start_waiting_until_resource(predicate).await.wait().await`.
+	pub async fn wait_until_resource<R: Resource + Clone>(
+		&self,
+		predicate: impl Fn(&R) -> bool + Send + Sync + 'static,
+	) -> R {
+		self.start_waiting_until_resource(predicate)
+			.await
+			.wait()
+			.await
+	}
+
 	/// Send a `Message` to the bevy world.
 	pub async fn send_message<M: Message>(&self, message: M) {
 		self.apply(WriteMessage(message)).await;
 	}
 
+	/// Send a `Message` to the bevy world. Returns an error if the message type is not registered.
+	pub async fn try_send_message<M: Message>(&self, message: M) -> Result<(), AsyncEcsError> {
+		self.with_world(try_write_message(message)).await
+	}
+
 	/// Start listening for `Message`s coming from the main bevy world.
 	/// Returns an `AsyncMessages` which can be further waited to receive these messages.
 	///
@@ -153,7 +409,79 @@ impl AsyncWorld {
 	pub async fn start_waiting_for_messages<M: Message + Clone>(&self) -> AsyncMessages<M> {
 		let (start_waiting_for, rx) = StartWaitingFor::messages();
 		self.apply(start_waiting_for).await;
-		AsyncMessages(rx)
+		AsyncMessages {
+			rx: Box::pin(rx),
+			dropped: Arc::default(),
+			world: self.clone(),
+			_message: PhantomData,
+		}
+	}
+
+	/// Start listening for `Message`s coming from the main bevy world that satisfy the given
+	/// predicate. Returns an `AsyncMessages` which can be further waited to receive these messages.
+	///
+	/// The predicate is evaluated inside the Bevy world, so messages that do not satisfy it are
+	/// never cloned or sent across the channel.
+	pub async fn start_waiting_for_messages_filtered<M: Message + Clone>(
+		&self,
+		predicate: impl Fn(&M) -> bool + Send + Sync + 'static,
+	) -> AsyncMessages<M> {
+		let (start_waiting_for, rx) = StartWaitingFor::messages();
+		self.apply(start_waiting_for.until(predicate)).await;
+		AsyncMessages {
+			rx: Box::pin(rx),
+			dropped: Arc::default(),
+			world: self.clone(),
+			_message: PhantomData,
+		}
+	}
+
+	/// Start listening for `Message`s coming from the main bevy world, mapping each one with the
+	/// given projection. Returns an `AsyncMessages` which can be further waited to receive the
+	/// projected values.
+	///
+	/// The projection is evaluated inside the Bevy world, so the message does not need to be
+	/// `Clone`.
+	pub async fn start_waiting_for_messages_map<M: Message, T: Send + 'static>(
+		&self,
+		project: impl Fn(&M) -> T + Send + Sync + 'static,
+	) -> AsyncMessages<M, T> {
+		let (start_waiting_for, rx) = StartWaitingFor::messages_map(project);
+		self.apply(start_waiting_for).await;
+		AsyncMessages {
+			rx: Box::pin(rx),
+			dropped: Arc::default(),
+			world: self.clone(),
+			_message: PhantomData,
+		}
+	}
+
+	/// Start listening for `Message`s coming from the main bevy world, holding at most `capacity`
+	/// unreceived messages at a time. When the subscription is full, new messages are handled
+	/// according to the given `Backpressure` policy. `Backpressure::Buffer` holds back up to another
+	/// `capacity` messages inside the bevy world, so memory use stays bounded with every policy, and
+	/// every policy drops messages once it runs out of room (see `AsyncMessages::dropped()`).
+	/// Returns an `AsyncMessages` which can be further waited to receive these messages.
+	///
+	/// Use this instead of `AsyncWorld::start_waiting_for_messages()` when the messages may be
+	/// written faster than they are received.
+	///
+	/// # Panics
+	///
+	/// Panics if `capacity` is zero.
+	pub async fn start_waiting_for_messages_with<M: Message + Clone>(
+		&self,
+		capacity: usize,
+		policy: Backpressure,
+	) -> AsyncMessages<M> {
+		let (start_waiting_for, rx, dropped) = StartWaitingFor::messages_with(capacity, policy);
+		self.apply(start_waiting_for).await;
+		AsyncMessages {
+			rx: Box::pin(rx),
+			dropped,
+			world: self.clone(),
+			_message: PhantomData,
+		}
 	}
 
 	/// Wait for the `Message` of a given type. Returns the value of the message, once it is received.
@@ -164,6 +492,16 @@ impl AsyncWorld {
 		self.start_waiting_for_messages().await.wait().await
 	}
 
+	/// Start observing the [`Event`] of a given type. Returns a `Stream` which yields every such
+	/// event that is triggered.
+	///
+	/// Dropping the stream despawns the [`Observer`].
+	pub async fn observe<E: Event + Clone>(&self) -> impl Stream<Item = E> + Send + use<E> {
+		let (start_observing, rx) = StartObserving::new(None);
+		self.apply(start_observing).await;
+		rx
+	}
+
 	/// Triggers the given [`Event`], which will run any [`Observer`]s watching for it.
 	pub async fn trigger<'a, T: Default, E: Event<Trigger<'a> = T> + Send + Sync + 'static>(
 		&self,
@@ -171,6 +509,16 @@ impl AsyncWorld {
 	) {
 		self.apply(trigger_event(event)).await;
 	}
+
+	/// Triggers the given [`Event`] using the given [`Trigger`](bevy_ecs::event::Trigger), which
+	/// will run any [`Observer`]s watching for it.
+	pub async fn trigger_with<E: Event<Trigger<'static>: Send + Sync>>(
+		&self,
+		event: E,
+		trigger: E::Trigger<'static>,
+	) {
+		self.apply(trigger_event_with(event, trigger)).await;
+	}
 }
 
 impl From<CommandQueueSender> for AsyncWorld {
@@ -181,30 +529,43 @@ impl From<CommandQueueSender> for AsyncWorld {
 
 impl FromWorld for AsyncWorld {
 	fn from_world(world: &mut World) -> Self {
-		let (sender, receiver) = async_channel::unbounded();
-		world.spawn((
-			CommandQueueReceiver::new(receiver),
-			Name::new("CommandQueueReceiver"),
-		));
-		CommandQueueSender::new(sender).into()
+		let (sender, receiver) = command::channel(world);
+		world.spawn((receiver, Name::new("CommandQueueReceiver")));
+		sender.into()
 	}
 }
 
 /// Represents a `Resource` being retrieved.
 ///
 /// The easiest way to get an `AsyncResource` is with `AsyncWorld::start_waiting_for_resource()`.
-pub struct AsyncResource<R: Resource>(Receiver<R>);
+/// The received value is of type `T`, which is the resource itself unless it was mapped with
+/// `AsyncWorld::start_waiting_for_resource_map()`.
+///
+/// Dropping an `AsyncResource` cancels the wait;
+/// the corresponding waiter is cleaned up on the next frame.
+pub struct AsyncResource<R: Resource, T = R> {
+	rx: Receiver<T>,
+	world: AsyncWorld,
+	_resource: PhantomData<fn() -> R>,
+}
 
-impl<R: Resource> fmt::Debug for AsyncResource<R> {
+impl<R: Resource, T> fmt::Debug for AsyncResource<R, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "AsyncResource(..)")
 	}
 }
 
-impl<R: Resource> AsyncResource<R> {
+impl<R: Resource, T: Send + 'static> AsyncResource<R, T> {
 	/// Wait for the `Resource` to exist, and retrieve its value.
-	pub async fn wait(self) -> R {
-		recv(self.0).await
+	pub async fn wait(self) -> T {
+		recv(self.rx).await
+	}
+
+	/// Wait for the `Resource` to exist, and retrieve its value. Returns `None` if the `Timeout`
+	/// expires first.
+	pub async fn wait_timeout(self, timeout: impl Into<Timeout>) -> Option<T> {
+		let Self { rx, world, .. } = self;
+		with_timeout(&world, timeout.into(), async move { rx.recv().await.ok() }).await
 	}
 }
 
@@ -222,18 +583,255 @@ impl<M: Message> Command for WriteMessage<M> {
 /// Represents Bevy `Message`s being received asynchronously
 ///
 /// The easiest way to get an `AsyncMessages` is with `AsyncWorld::start_waiting_for_messages()`.
-pub struct AsyncMessages<M: Message>(Receiver<M>);
+/// The received values are of type `T`, which is the message itself unless it was mapped with
+/// `AsyncWorld::start_waiting_for_messages_map()`.
+///
+/// Dropping an `AsyncMessages` cancels the subscription;
+/// the corresponding waiter is cleaned up on the next frame.
+///
+/// `AsyncMessages` is also a `Stream` of the received messages, which ends once the vanilla Bevy
+/// world is dropped.
+pub struct AsyncMessages<M: Message, T = M> {
+	rx: Pin<Box<Receiver<T>>>,
+	dropped: Arc<AtomicUsize>,
+	world: AsyncWorld,
+	_message: PhantomData<fn() -> M>,
+}
 
-impl<M: Message> fmt::Debug for AsyncMessages<M> {
+impl<M: Message, T> fmt::Debug for AsyncMessages<M, T> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(f, "AsyncMessages(..)")
 	}
 }
 
-impl<M: Message> AsyncMessages<M> {
+impl<M: Message, T> Stream for AsyncMessages<M, T> {
+	type Item = T;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.rx.as_mut().poll_next(cx)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.rx.size_hint()
+	}
+}
+
+impl<M: Message, T: Send + 'static> AsyncMessages<M, T> {
+	/// Returns the number of messages that have been dropped so far because the subscription was
+	/// full. This is always zero for unbounded subscriptions.
+	pub fn dropped(&self) -> usize {
+		self.dropped.load(Ordering::Relaxed)
+	}
+
+	/// Converts this `AsyncMessages` into a plain `Stream` of the received messages.
+	pub fn into_stream(self) -> impl Stream<Item = T> + Send + Unpin + use<M, T> {
+		self.rx
+	}
+
 	/// Wait for a `Message` to be received from the vanilla Bevy world. This function can be called repeatedly
 	/// to get more messages as they are received.
-	pub async fn wait(&self) -> M {
-		recv(self.0.clone()).await
+	pub async fn wait(&self) -> T {
+		recv(Receiver::clone(&self.rx)).await
+	}
+
+	/// Wait for a `Message` to be received from the vanilla Bevy world. Returns `None` if the
+	/// `Timeout` expires first.
+	pub async fn wait_timeout(&self, timeout: impl Into<Timeout>) -> Option<T> {
+		let fut = async { self.rx.recv().await.ok() };
+		with_timeout(&self.world, timeout.into(), fut).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::AsyncEcsError;
+	use crate::AsyncEcsPlugin;
+	use crate::AsyncWorld;
+	use bevy::prelude::*;
+	use bevy::tasks::AsyncComputeTaskPool;
+	use futures_lite::StreamExt;
+
+	#[derive(Clone, Message)]
+	struct Registered;
+
+	#[derive(Clone, Message)]
+	struct Unregistered;
+
+	#[test]
+	fn try_send_message() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.add_message::<Registered>();
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let registered = async_world.try_send_message(Registered).await;
+				let unregistered = async_world.try_send_message(Unregistered).await;
+				sender.send((registered, unregistered)).await.unwrap();
+			})
+			.detach();
+
+		let (registered, unregistered) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert!(registered.is_ok());
+		assert!(matches!(
+			unregistered,
+			Err(AsyncEcsError::MessageNotRegistered(_))
+		));
+	}
+
+	#[test]
+	fn with_world() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let id = async_world
+					.with_world(|world: &mut World| world.spawn(Name::new("Frank")).id())
+					.await;
+				let name = async_world
+					.with_world_ref(move |world: &World| world.get::<Name>(id).cloned())
+					.await;
+				sender.send((id, name)).await.unwrap();
+			})
+			.detach();
+
+		let (id, name) = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!(Some(Name::new("Frank")), name);
+		assert!(app.world().get_entity(id).is_ok());
+	}
+
+	#[derive(Clone, Resource)]
+	struct Counter(u8);
+
+	#[test]
+	fn resource() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.insert_resource(Counter(4));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let before = async_world.get_resource::<Counter>().await.unwrap();
+				let previous = async_world
+					.resource_scope(|counter: &mut Counter| {
+						counter.0 += 1;
+						counter.0 - 1
+					})
+					.await;
+				async_world.remove_resource::<Counter>().await;
+				let missing = async_world.get_resource::<Counter>().await;
+				let not_run = async_world
+					.resource_scope(|counter: &mut Counter| counter.0)
+					.await;
+				sender
+					.send((before.0, previous, missing.is_none(), not_run))
+					.await
+					.unwrap();
+			})
+			.detach();
+
+		let results = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!((4, Some(4), true, None), results);
+	}
+
+	#[test]
+	fn run_system_once_with() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.insert_resource(Counter(4));
+
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		fn add(In(n): In<u8>, counter: Res<Counter>) -> u8 {
+			counter.0 + n
+		}
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let sum = async_world.run_system_once_with(add, 2).await;
+				async_world.remove_resource::<Counter>().await;
+				let skipped = async_world.run_system_once_with(add, 2).await;
+				sender.send((sum.ok(), skipped.is_err())).await.unwrap();
+			})
+			.detach();
+
+		let results = loop {
+			match receiver.try_recv() {
+				Ok(results) => break results,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!((Some(6), true), results);
+	}
+
+	#[test]
+	fn messages_stream() {
+		let mut app = App::new();
+		app.add_plugins((MinimalPlugins, AsyncEcsPlugin))
+			.add_message::<Registered>();
+
+		let (ready_tx, ready_rx) = async_channel::bounded(1);
+		let (sender, receiver) = async_channel::bounded(1);
+		let async_world = AsyncWorld::from_world(app.world_mut());
+
+		AsyncComputeTaskPool::get()
+			.spawn(async move {
+				let messages = async_world.start_waiting_for_messages::<Registered>().await;
+				let other = async_world.start_waiting_for_messages::<Registered>().await;
+				ready_tx.send(()).await.unwrap();
+				let count = messages.take(3).count().await;
+				let other: Vec<_> = other.into_stream().take(3).collect().await;
+				sender.send((count, other.len())).await.unwrap();
+			})
+			.detach();
+
+		while ready_rx.try_recv().is_err() {
+			app.update();
+		}
+		app.update();
+
+		for _ in 0..3 {
+			app.world_mut().write_message(Registered);
+			app.update();
+		}
+
+		let counts = loop {
+			match receiver.try_recv() {
+				Ok(counts) => break counts,
+				Err(_) => app.update(),
+			}
+		};
+
+		assert_eq!((3, 3), counts);
 	}
 }